@@ -0,0 +1,183 @@
+use crate::error::DaedalusError;
+use std::str::FromStr;
+
+// Default limit of scenarios created by the cartesian product for each endpoint
+const CARTESIAN_LIMIT: usize = 1000;
+
+/// How the values of the different parts of the request are combined into scenarios.
+///
+/// All the strategies start with a scenario using the first proper value of each part and
+/// change one part at a time from there, so every failing scenario has a single error.
+/// The strategies differ in how they combine the passing values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CombinationStrategy {
+    /// Only one part of the request changes in each scenario. This is the default.
+    OneAtATime,
+    /// Every pair of passing values of two different parts appears in at least one scenario.
+    Pairwise,
+    /// All the combinations of passing values, up to a limit per endpoint.
+    Cartesian {
+        /// Maximum number of combinations of passing values for each endpoint
+        limit: usize,
+    },
+}
+
+/// Parses `one-at-a-time`, `pairwise`, `cartesian` and `cartesian:<limit>`.
+impl FromStr for CombinationStrategy {
+    type Err = DaedalusError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let unknown = || DaedalusError::UnknownCombination {
+            name: name.to_string(),
+        };
+        match name {
+            "one-at-a-time" => Ok(CombinationStrategy::OneAtATime),
+            "pairwise" => Ok(CombinationStrategy::Pairwise),
+            "cartesian" => Ok(CombinationStrategy::Cartesian {
+                limit: CARTESIAN_LIMIT,
+            }),
+            _ => match name.strip_prefix("cartesian:") {
+                Some(limit) => limit
+                    .parse()
+                    .map(|limit| CombinationStrategy::Cartesian { limit })
+                    .map_err(|_| unknown()),
+                None => Err(unknown()),
+            },
+        }
+    }
+}
+
+impl CombinationStrategy {
+    // Each column has the values of one part of the request, with the passing values first.
+    // Returns the rows, the values used in each scenario. The first row uses the first value of each column.
+    pub(crate) fn combine<'a, T>(
+        &self,
+        columns: &[Vec<&'a T>],
+        is_passing: impl Fn(&T) -> bool,
+    ) -> Vec<Vec<&'a T>> {
+        let mut rows = one_at_a_time(columns);
+        let passing: Vec<Vec<&'a T>> = columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .copied()
+                    .filter(|value| is_passing(value))
+                    .collect()
+            })
+            .collect();
+        let extra = match self {
+            CombinationStrategy::OneAtATime => vec![],
+            CombinationStrategy::Pairwise => pairwise(&passing),
+            CombinationStrategy::Cartesian { limit } => cartesian(&passing, *limit),
+        };
+        for row in extra {
+            if !rows.iter().any(|existing| same_row(existing, &row)) {
+                rows.push(row);
+            }
+        }
+        rows
+    }
+}
+
+// The first value of each column, then every other value changing one column at a time
+fn one_at_a_time<'a, T>(columns: &[Vec<&'a T>]) -> Vec<Vec<&'a T>> {
+    let first: Vec<&T> = columns.iter().map(|column| column[0]).collect();
+    let mut rows = vec![first.clone()];
+    for (i, column) in columns.iter().enumerate() {
+        for value in column.iter().skip(1) {
+            let mut row = first.clone();
+            row[i] = value;
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+// Greedy all-pairs: each new row covers a pair not covered yet and as many other pairs as possible
+fn pairwise<'a, T>(columns: &[Vec<&'a T>]) -> Vec<Vec<&'a T>> {
+    let mut uncovered = Vec::new();
+    for i in 0..columns.len() {
+        for j in i + 1..columns.len() {
+            for a in 0..columns[i].len() {
+                for b in 0..columns[j].len() {
+                    uncovered.push((i, a, j, b));
+                }
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    while let Some(&(i, a, j, b)) = uncovered.first() {
+        let mut chosen: Vec<Option<usize>> = vec![None; columns.len()];
+        chosen[i] = Some(a);
+        chosen[j] = Some(b);
+        for column in 0..columns.len() {
+            if chosen[column].is_some() {
+                continue;
+            }
+            let covered_by = |value: usize| {
+                uncovered
+                    .iter()
+                    .filter(|&&(x, xa, y, yb)| {
+                        (x == column && xa == value && chosen[y] == Some(yb))
+                            || (y == column && yb == value && chosen[x] == Some(xa))
+                    })
+                    .count()
+            };
+            // Ties go to the first value, usually the proper one
+            let best = (0..columns[column].len())
+                .rev()
+                .max_by_key(|&value| covered_by(value))
+                .unwrap_or(0);
+            chosen[column] = Some(best);
+        }
+        let chosen: Vec<usize> = chosen.into_iter().map(|value| value.unwrap_or(0)).collect();
+        uncovered.retain(|&(x, xa, y, yb)| !(chosen[x] == xa && chosen[y] == yb));
+        rows.push(
+            chosen
+                .iter()
+                .enumerate()
+                .map(|(column, &value)| columns[column][value])
+                .collect(),
+        );
+    }
+    rows
+}
+
+// All the combinations in order, until the limit
+fn cartesian<'a, T>(columns: &[Vec<&'a T>], limit: usize) -> Vec<Vec<&'a T>> {
+    let mut rows = Vec::new();
+    let mut indexes = vec![0; columns.len()];
+    while rows.len() < limit {
+        rows.push(
+            indexes
+                .iter()
+                .enumerate()
+                .map(|(column, &value)| columns[column][value])
+                .collect(),
+        );
+        // Next combination, the last column changes faster
+        let mut column = columns.len();
+        loop {
+            if column == 0 {
+                return rows;
+            }
+            column -= 1;
+            indexes[column] += 1;
+            if indexes[column] < columns[column].len() {
+                break;
+            }
+            indexes[column] = 0;
+        }
+    }
+    rows
+}
+
+fn same_row<T>(one: &[&T], other: &[&T]) -> bool {
+    one.len() == other.len()
+        && one
+            .iter()
+            .zip(other.iter())
+            .all(|(a, b)| std::ptr::eq(*a, *b))
+}
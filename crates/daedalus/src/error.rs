@@ -15,4 +15,65 @@ pub enum DaedalusError {
         #[source]
         source: std::io::Error,
     },
+
+    #[error("Could not write the file `{filename}`.")]
+    Writing {
+        filename: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Could not serialize `{filename}` as json.")]
+    JsonSerializing {
+        filename: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Could not serialize `{filename}` as yaml.")]
+    YamlSerializing {
+        filename: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    #[error("Unknown combination strategy `{name}`. Use one-at-a-time, pairwise, cartesian or cartesian:<limit>.")]
+    UnknownCombination { name: String },
+
+    #[error("The reference `{reference}` in `{filename}` could not be resolved, `{pointer}` was not found in `{target}`.")]
+    UnresolvedReference {
+        reference: String,
+        filename: String,
+        target: String,
+        pointer: String,
+    },
+
+    #[error("The reference `{reference}` in `{filename}` is part of a cycle: {cycle}")]
+    ReferenceCycle {
+        reference: String,
+        filename: String,
+        cycle: String,
+    },
+
+    #[error("`{pattern}` is not a valid regular expression.")]
+    InvalidRegex {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+
+    #[error("No server in the openapi file matches `{selection}`.")]
+    ServerNotFound { selection: String },
+
+    #[error(
+        "`{value}` is not a possible value of the server variable `{name}`. Use one of: {allowed}."
+    )]
+    ServerVariable {
+        name: String,
+        value: String,
+        allowed: String,
+    },
+
+    #[error("The server url `{url}` uses the variable `{name}` but it has no value.")]
+    MissingServerVariable { name: String, url: String },
 }
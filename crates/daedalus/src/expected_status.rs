@@ -0,0 +1,94 @@
+use crate::mutation::instructions::{Mutagen, RequestPart};
+use crate::mutation::Mutation;
+use http::StatusCode;
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+// What the status applies to, the snake_case name of a mutagen or of a whole request part.
+// Ex. `wrong_pattern: 400` or `any_param: 400`
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Target {
+    Mutagen(Mutagen),
+    RequestPart(RequestPart),
+}
+
+impl TryFrom<&str> for Target {
+    type Error = String;
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+            name.into_deserializer();
+        Target::deserialize(deserializer)
+            .map_err(|_| format!("`{}` is not the name of a mutagen or a request part", name))
+    }
+}
+
+type RawStatuses = BTreeMap<String, BTreeMap<String, u16>>;
+
+// The expectations section of the conversions file. Status codes per path, the path "/" applies to all paths.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(try_from = "RawStatuses")]
+pub(crate) struct ExpectedStatuses {
+    paths: BTreeMap<String, Vec<(Target, StatusCode)>>,
+}
+
+impl TryFrom<RawStatuses> for ExpectedStatuses {
+    type Error = String;
+    fn try_from(raw: RawStatuses) -> Result<Self, Self::Error> {
+        let mut paths = BTreeMap::new();
+        for (path, statuses) in raw {
+            let mut targets = Vec::new();
+            for (name, code) in statuses {
+                let status = StatusCode::from_u16(code)
+                    .map_err(|_| format!("`{}` is not a valid status code for `{}`", code, name))?;
+                targets.push((Target::try_from(name.as_str())?, status));
+            }
+            paths.insert(path, targets);
+        }
+        Ok(ExpectedStatuses { paths })
+    }
+}
+
+impl ExpectedStatuses {
+    // Sets the configured status of the mutation for this endpoint, if any.
+    // The most specific path wins and within a path a mutagen wins over its request part.
+    // Only failing mutations change, some mutagens like `none` also pass for optional parameters.
+    pub(crate) fn apply(&self, path_name: &str, mutation: &mut Mutation) {
+        if mutation.mutagen.expected.is_success() {
+            return;
+        }
+        let mut matching: Vec<_> = self
+            .paths
+            .iter()
+            .filter(|(path, _)| path_name.contains(path.as_str()))
+            .collect();
+        matching.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+
+        let mutagen = Target::Mutagen(mutation.mutagen.mutagen.clone());
+        let request_part = Target::RequestPart(mutation.mutagen.request_part.clone());
+        // Required and optional parameters are also any parameter
+        let any_param = Target::RequestPart(RequestPart::AnyParam);
+        let is_param = matches!(
+            mutation.mutagen.request_part,
+            RequestPart::RequiredParam | RequestPart::OptionalParam
+        );
+        for (_, targets) in matching {
+            let status = Self::status_for(targets, &mutagen)
+                .or_else(|| Self::status_for(targets, &request_part))
+                .or_else(|| Self::status_for(targets, &any_param).filter(|_| is_param));
+            if let Some(status) = status {
+                mutation.mutagen.expected = status;
+                return;
+            }
+        }
+    }
+
+    fn status_for(targets: &[(Target, StatusCode)], target: &Target) -> Option<StatusCode> {
+        targets
+            .iter()
+            .find(|(configured, _)| configured == target)
+            .map(|(_, status)| *status)
+    }
+}
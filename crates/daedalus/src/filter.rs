@@ -0,0 +1,63 @@
+use crate::error::DaedalusError;
+use regex::Regex;
+
+// Selects the operations scenarios are generated for.
+// All the kinds of filters must accept an operation, any of the values of a kind is enough.
+pub(crate) struct OperationFilter {
+    matches: Regex,
+    excludes: Vec<Regex>,
+    methods: Vec<String>,
+    operation_ids: Vec<Regex>,
+    tags: Vec<String>,
+}
+
+impl OperationFilter {
+    pub(crate) fn new(
+        matches: &str,
+        excludes: &[String],
+        methods: &[String],
+        operation_ids: &[String],
+        tags: &[String],
+    ) -> Result<Self, DaedalusError> {
+        Ok(OperationFilter {
+            matches: regex(matches)?,
+            excludes: excludes
+                .iter()
+                .map(|pattern| regex(pattern))
+                .collect::<Result<_, _>>()?,
+            methods: methods.iter().map(|method| method.to_uppercase()).collect(),
+            operation_ids: operation_ids
+                .iter()
+                .map(|pattern| regex(pattern))
+                .collect::<Result<_, _>>()?,
+            tags: tags.to_vec(),
+        })
+    }
+
+    pub(crate) fn path(&self, path_name: &str) -> bool {
+        self.matches.is_match(path_name)
+            && !self
+                .excludes
+                .iter()
+                .any(|exclude| exclude.is_match(path_name))
+    }
+
+    pub(crate) fn operation(&self, method: &str, operation: &openapiv3::Operation) -> bool {
+        let method_matches = self.methods.is_empty() || self.methods.iter().any(|m| m == method);
+        let id_matches = self.operation_ids.is_empty()
+            || operation
+                .operation_id
+                .iter()
+                .any(|id| self.operation_ids.iter().any(|re| re.is_match(id)));
+        let tag_matches =
+            self.tags.is_empty() || operation.tags.iter().any(|tag| self.tags.contains(tag));
+        method_matches && id_matches && tag_matches
+    }
+}
+
+fn regex(pattern: &str) -> Result<Regex, DaedalusError> {
+    Regex::new(pattern).map_err(|source| DaedalusError::InvalidRegex {
+        pattern: pattern.to_string(),
+        source,
+    })
+}
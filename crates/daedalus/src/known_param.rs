@@ -3,6 +3,8 @@ use serde::Deserialize;
 use std::collections::BTreeMap;
 
 use crate::error::DaedalusError;
+use crate::expected_status::ExpectedStatuses;
+use crate::random;
 use crate::spec;
 
 // This is the values of the conversion
@@ -29,7 +31,13 @@ enum Raw {
 
 #[derive(Debug, PartialEq, Deserialize)]
 pub(crate) struct Conversions {
+    #[serde(default)]
     paths: BTreeMap<String, BTreeMap<String, StringOrArray>>,
+    #[serde(default)]
+    expectations: ExpectedStatuses,
+    // The credentials for each security scheme, by the name of the scheme
+    #[serde(default)]
+    credentials: BTreeMap<String, String>,
 }
 
 impl Conversions {
@@ -39,10 +47,20 @@ impl Conversions {
         } else {
             Ok(Conversions {
                 paths: BTreeMap::new(),
+                expectations: ExpectedStatuses::default(),
+                credentials: BTreeMap::new(),
             })
         }
     }
 
+    pub(crate) fn expectations(&self) -> &ExpectedStatuses {
+        &self.expectations
+    }
+
+    pub(crate) fn credentials(&self) -> &BTreeMap<String, String> {
+        &self.credentials
+    }
+
     pub(crate) fn for_path<'a>(&'a self, pattern: &str) -> ConversionView {
         let mut result = BTreeMap::new();
         for (path, keys) in &self.paths {
@@ -85,11 +103,8 @@ impl<'a> ConversionView<'a> {
         let result = not_default.or(the_default).or(None);
 
         result.and_then(|inner_hashmap| {
-            inner_hashmap
-                .get(name)
-                .unwrap()
-                .0
-                .choose(&mut rand::thread_rng()) //choose returns an Option, we connect to it with and_then
+            let values = &inner_hashmap.get(name).unwrap().0;
+            random::with_rng(|rng| values.choose(rng)) //choose returns an Option, we connect to it with and_then
         })
     }
 
@@ -101,7 +116,7 @@ impl<'a> ConversionView<'a> {
         for (path, inner_hash) in &self.paths {
             let mut result = pattern.to_owned();
             for (key, value) in *inner_hash {
-                let random_value = &value.0.choose(&mut rand::thread_rng()).unwrap();
+                let random_value = random::with_rng(|rng| value.0.choose(rng)).unwrap();
                 // We want to accumulate the changes of result on itself so things with multiple variables like
                 // /resource/{id}{tag} gets every variable replaced and saved
                 result = str::replace(&result, &format!("{{{}}}", key), random_value)
@@ -1,23 +1,43 @@
 /*!
 The Daedalus crate provides auto-generation of test scenarios out of openapi files.
+
+Extra mutations can be added implementing a [`MutationProvider`] and registering it with [`Generator::register`].
 */
 
 #![deny(missing_docs)]
 
+mod combination;
 mod error;
+mod expected_status;
+mod filter;
 mod known_param;
 mod mutation;
+mod openapi31;
 mod operation;
+mod provider;
+mod random;
+mod reference;
 mod request;
 mod request_param;
 mod scenario;
+mod server;
 mod spec;
+mod status_code;
+mod swagger2;
 
-use openapi_utils::{ReferenceOrExt, ServerExt, SpecExt};
+use http::StatusCode;
+use openapi_utils::{ReferenceOrExt, SpecExt};
+use std::collections::BTreeMap;
 
+pub use combination::CombinationStrategy;
+pub use mutation::instructions::{Mutagen, MutagenInstruction, RequestPart};
+pub use mutation::Mutation;
+pub use operation::{Crud, Endpoint};
+pub use provider::MutationProvider;
 pub use request::Request;
 pub use scenario::Scenario;
 pub use scenario::ScenarioExpectation;
+pub use server::ServerSelection;
 
 /// Configuration for the generation of the scenarios.
 pub struct GeneratorConfig {
@@ -29,6 +49,26 @@ pub struct GeneratorConfig {
     scenarios_all_codes: bool,
     /// Regex string. Only scenarios for paths matching this string will be generated
     matches: String,
+    /// Regex strings. Paths matching any of these are skipped
+    excludes: Vec<String>,
+    /// Only operations with one of these HTTP methods are used, all when empty
+    methods: Vec<String>,
+    /// Regex strings. Only operations with an operationId matching one of these are used, all when empty
+    operation_ids: Vec<String>,
+    /// Only operations with one of these tags are used, all when empty
+    tags: Vec<String>,
+    /// The status code expected when a path parameter has a wrong value
+    path_error_status: StatusCode,
+    /// If we want a passing scenario for each of the values of the enumerations
+    scenarios_all_enum_values: bool,
+    /// How the values of the parts of the request are combined
+    combination: CombinationStrategy,
+    /// Seed of all the random values, the same seed generates the same scenarios
+    seed: u64,
+    /// Which of the servers of the openapi file is used
+    server: ServerSelection,
+    /// Values for the variables of the server URLs, instead of their defaults
+    server_variables: BTreeMap<String, String>,
 }
 
 impl GeneratorConfig {
@@ -44,46 +84,207 @@ impl GeneratorConfig {
             conv_filename,
             scenarios_all_codes,
             matches,
+            excludes: Vec::new(),
+            methods: Vec::new(),
+            operation_ids: Vec::new(),
+            tags: Vec::new(),
+            path_error_status: StatusCode::NOT_FOUND,
+            scenarios_all_enum_values: false,
+            combination: CombinationStrategy::OneAtATime,
+            seed: rand::random(),
+            server: ServerSelection::Index(0),
+            server_variables: BTreeMap::new(),
         }
     }
+
+    /// Skips the paths matching this regular expression. Can be called several times.
+    pub fn exclude(&mut self, pattern: &str) -> &mut Self {
+        self.excludes.push(pattern.to_string());
+        self
+    }
+
+    /// Only uses operations with this HTTP method. Can be called several times to use several methods.
+    pub fn method(&mut self, method: &str) -> &mut Self {
+        self.methods.push(method.to_string());
+        self
+    }
+
+    /// Only uses operations whose operationId matches this regular expression.
+    /// Can be called several times, matching any of them is enough.
+    pub fn operation_id(&mut self, pattern: &str) -> &mut Self {
+        self.operation_ids.push(pattern.to_string());
+        self
+    }
+
+    /// Only uses operations with this tag. Can be called several times, having any of them is enough.
+    pub fn tag(&mut self, tag: &str) -> &mut Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Sets the status code expected when a path parameter has a wrong value, 404 by default.
+    /// Some services validate the path parameters and return a 422 instead.
+    pub fn path_error_status(&mut self, status: StatusCode) -> &mut Self {
+        self.path_error_status = status;
+        self
+    }
+
+    /// Generates a passing scenario for each of the values of string and integer enumerations.
+    /// By default only one valid value is used.
+    pub fn all_enum_values(&mut self, enabled: bool) -> &mut Self {
+        self.scenarios_all_enum_values = enabled;
+        self
+    }
+
+    /// Sets how the values of the parts of the request are combined into scenarios.
+    /// By default only one part changes in each scenario.
+    pub fn combination(&mut self, strategy: CombinationStrategy) -> &mut Self {
+        self.combination = strategy;
+        self
+    }
+
+    /// Sets the seed of the random values used in the scenarios. A run can be reproduced using its seed.
+    /// By default a random seed is used.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Selects the server of the openapi file whose base path is added to the paths, the first one by default.
+    /// Paths and operations with their own servers use the one matching the selection or their first one.
+    pub fn server(&mut self, selection: ServerSelection) -> &mut Self {
+        self.server = selection;
+        self
+    }
+
+    /// Sets the value of a variable of the server URLs, instead of its default.
+    /// The value must be one of the enumeration of the variable if it has one.
+    pub fn server_variable(&mut self, name: &str, value: &str) -> &mut Self {
+        self.server_variables
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Writes the scenarios to a file, as JSON when the file name ends in `.json` and as YAML otherwise.
+pub fn write_scenarios(scenarios: &[Scenario], filename: &str) -> Result<(), error::DaedalusError> {
+    spec::write(filename, &scenarios)
+}
+
+/// Reads scenarios from a JSON or YAML file, as written by [`write_scenarios`].
+pub fn read_scenarios(filename: &str) -> Result<Vec<Scenario>, error::DaedalusError> {
+    spec::read(filename)
 }
 
 /// This is the builder object in this library it creates the scenarios you can work with.
 pub struct Generator {
     mutator: mutation::Mutator,
     endpoints: Vec<operation::Endpoint>,
+    server_url: Option<String>,
 }
 impl Generator {
     /// Construction out of the configuration
     pub fn new(config: &GeneratorConfig) -> Result<Self, error::DaedalusError> {
-        let spec: openapiv3::OpenAPI = spec::read(&config.filename)?;
+        let spec = spec::read_openapi(&config.filename)?;
         let spec = spec.deref_all();
-        let mutator = mutation::Mutator::new(&config.conv_filename, config.scenarios_all_codes)?;
-        let endpoints = Self::endpoints(spec, &config.matches);
-        Ok(Generator { mutator, endpoints })
+        let mut mutator = mutation::Mutator::new(
+            &config.conv_filename,
+            config.scenarios_all_codes,
+            config.path_error_status,
+            config.scenarios_all_enum_values,
+            config.combination.clone(),
+            config.seed,
+        )?;
+        mutator.security_schemes(Self::security_schemes(&spec));
+        let servers = server::Servers::new(config.server.clone(), config.server_variables.clone());
+        let server_url = servers.spec_url(&spec.servers)?;
+        let filter = filter::OperationFilter::new(
+            &config.matches,
+            &config.excludes,
+            &config.methods,
+            &config.operation_ids,
+            &config.tags,
+        )?;
+        let endpoints = Self::endpoints(spec, &filter, &servers, &server_url)?;
+        Ok(Generator {
+            mutator,
+            endpoints,
+            server_url,
+        })
+    }
+
+    /// Registers a provider of extra mutations, they are added to the ones created by the generator.
+    pub fn register<P: MutationProvider + 'static>(&mut self, provider: P) -> &mut Self {
+        self.mutator.register(Box::new(provider));
+        self
+    }
+
+    /// The seed of the random values, use it to generate the same scenarios again
+    pub fn seed(&self) -> u64 {
+        self.mutator.seed()
+    }
+
+    /// The scheme and host of the selected server with its variables replaced, ex. `https://staging.example.com`.
+    /// None when the openapi file has no servers or their URLs are relative.
+    /// Paths and operations with their own servers may have another one, see `Scenario::server_origin`.
+    pub fn server_origin(&self) -> Option<&str> {
+        self.server_url
+            .as_ref()
+            .and_then(|url| server::split(url).0)
     }
 
     /// Returns an iterator over the scenarios generated by the generator
-    pub fn scenarios(&self) -> impl Iterator<Item = crate::scenario::Scenario> {
+    pub fn scenarios(&self) -> impl Iterator<Item = crate::scenario::Scenario> + '_ {
         self.endpoints
             .iter()
-            .flat_map(move |e| self.mutator.mutate(&e))
-    }
-
-    fn endpoints(spec: openapiv3::OpenAPI, matches: &str) -> Vec<operation::Endpoint> {
-        let base_path = match spec.servers.first() {
-            None => String::from(""),
-            Some(server) => server.base_path(),
-        };
-        spec.paths
-            .into_iter()
-            .filter(|p| p.0.contains(&matches))
-            .flat_map(|(path_name, methods)| {
-                operation::Endpoint::new_supported(
-                    &format!("{}{}", base_path, path_name),
-                    methods.to_item_ref(),
-                )
+            .flat_map(move |e| self.mutator.mutate(e))
+    }
+
+    fn security_schemes(spec: &openapiv3::OpenAPI) -> BTreeMap<String, openapiv3::SecurityScheme> {
+        spec.components
+            .iter()
+            .flat_map(|components| components.security_schemes.iter())
+            .filter_map(|(name, scheme)| match scheme {
+                openapiv3::ReferenceOr::Item(scheme) => Some((name.clone(), scheme.clone())),
+                openapiv3::ReferenceOr::Reference { .. } => None,
             })
             .collect()
     }
+
+    // The base path of the closest servers, the operation ones, then the path ones and then the spec ones, goes before the path.
+    // Their origin is the one of the closest servers with an absolute URL.
+    fn endpoints(
+        spec: openapiv3::OpenAPI,
+        filter: &filter::OperationFilter,
+        servers: &server::Servers,
+        server_url: &Option<String>,
+    ) -> Result<Vec<operation::Endpoint>, error::DaedalusError> {
+        let mut endpoints = Vec::new();
+        let security = spec.security.clone();
+        let spec_origin = server::origin(server_url.as_deref());
+        for (path_name, methods) in spec.paths.iter().filter(|p| filter.path(p.0)) {
+            let methods = methods.to_item_ref();
+            let path_url = servers.override_url(&methods.servers)?;
+            let path_origin = server::origin(path_url.as_deref()).or_else(|| spec_origin.clone());
+            let path_url = path_url.or_else(|| server_url.clone());
+            let endpoints_of_path = operation::Endpoint::new_supported(path_name, methods)
+                .into_iter()
+                .filter(|e| filter.operation(e.crud.to_method_name(), &e.method));
+            for mut endpoint in endpoints_of_path {
+                let url = servers.override_url(&endpoint.method.servers)?;
+                endpoint.server_origin =
+                    server::origin(url.as_deref()).or_else(|| path_origin.clone());
+                let url = url.or_else(|| path_url.clone());
+                if let Some(url) = url {
+                    endpoint.path_name = format!("{}{}", server::split(&url).1, endpoint.path_name);
+                }
+                // Operations without their own security requirements use the ones of the spec
+                if endpoint.method.security.is_none() {
+                    endpoint.method.security = security.clone();
+                }
+                endpoints.push(endpoint);
+            }
+        }
+        Ok(endpoints)
+    }
 }
@@ -1,36 +1,64 @@
+use crate::combination::CombinationStrategy;
 use crate::error::DaedalusError;
 use crate::known_param::Conversions;
 use crate::operation::Endpoint;
+use crate::provider::MutationProvider;
+use crate::random;
 use crate::request::ScenarioRequest;
-use crate::request_param::RequestParam;
+use crate::request_param::{ParamLocation, RequestParam};
 use crate::scenario::Scenario;
 use http::StatusCode;
 use instructions::{Mutagen, MutagenInstruction, RequestPart};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use openapi_utils::{OperationExt, ParameterExt};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use tracing::{debug, warn};
 
+mod array_type;
+mod body_mutation;
 mod bool_type;
+mod credentials;
 pub mod instructions;
 mod integer_type;
+mod number_type;
+mod object_type;
 pub mod param_mutation;
 mod params;
+mod pattern;
+mod schema_value;
 mod string_type;
 
-#[derive(Debug, Clone)]
+/// A change to a part of the request and the status code it should receive
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mutation {
+    /// What was changed and what we expect
     pub mutagen: instructions::MutagenInstruction,
     payload: MutationValue,
 }
-#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord)]
-pub enum MutationValue {
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MutationValue {
     Value(String),
     Param(RequestParam),
+    Params(Vec<RequestParam>), // The same parameter repeated, ex. exploded arrays
+    Body { property: String, body: String },
 }
 
 impl Mutation {
+    /// A mutation sending `value` in `param`, where the parameter is located.
+    /// The parameter is sent there whatever the request part of the instruction is.
+    pub fn for_param(
+        instruction: instructions::MutagenInstruction,
+        param: &openapiv3::Parameter,
+        value: &str,
+    ) -> Self {
+        let request_param = RequestParam::new(&param.parameter_data().name, value)
+            .located(ParamLocation::from(param));
+        Mutation::new_param(instruction, request_param)
+    }
     pub(crate) fn new(mutagen: instructions::MutagenInstruction, value: String) -> Self {
         Mutation {
             mutagen,
@@ -46,20 +74,39 @@ impl Mutation {
             payload: MutationValue::Param(value),
         }
     }
-    fn value(&self) -> String {
-        match self.payload.clone() {
-            MutationValue::Value(value) => value,
-            MutationValue::Param(_) => {
-                unreachable!("Trying to access a param but we have a value.")
-            }
+    pub(crate) fn new_params(
+        mutagen: instructions::MutagenInstruction,
+        values: Vec<RequestParam>,
+    ) -> Self {
+        Mutation {
+            mutagen,
+            payload: MutationValue::Params(values),
         }
     }
-    fn param_value(&self) -> RequestParam {
-        match self.payload.clone() {
-            MutationValue::Param(param) => param,
-            MutationValue::Value(_) => {
-                unreachable!("Trying to access a value but we have a parameter.")
-            }
+    pub(crate) fn new_body(
+        mutagen: instructions::MutagenInstruction,
+        property: &str,
+        body: String,
+    ) -> Self {
+        Mutation {
+            mutagen,
+            payload: MutationValue::Body {
+                property: property.to_string(),
+                body,
+            },
+        }
+    }
+    // The name of what changed, the parameter or the property of the body
+    fn target(&self) -> String {
+        match &self.payload {
+            MutationValue::Value(_) => String::new(),
+            MutationValue::Param(param) => param.name.clone(),
+            MutationValue::Params(params) => params
+                .iter()
+                .map(|param| param.name.as_str())
+                .collect::<Vec<&str>>()
+                .join("&"),
+            MutationValue::Body { property, .. } => property.clone(),
         }
     }
 }
@@ -90,16 +137,43 @@ impl fmt::Display for Mutation {
 
         match &self.payload {
             MutationValue::Param(param) => {
+                if param.location == ParamLocation::Header
+                    || param.location == ParamLocation::Cookie
+                {
+                    write!(f, "  {}", param.location)?;
+                }
                 if let Some(value) = &param.value {
                     write!(f, "  \"{}={}\"", param.name, value)?;
                 } else {
                     write!(f, "  \"{}\"", param.name)?;
                 }
+            }
+            MutationValue::Params(params) => {
+                let pairs: Vec<String> = params
+                    .iter()
+                    .filter_map(|param| {
+                        param
+                            .value
+                            .as_ref()
+                            .map(|value| format!("{}={}", param.name, value))
+                    })
+                    .collect();
+                if pairs.is_empty() {
+                    write!(f, "  \"{}\"", params[0].name)?;
+                } else {
+                    write!(f, "  \"{}\"", pairs.join("&"))?;
+                }
                 //     format!("{} {}", self.mutagen.request_part, param.name)
             }
             MutationValue::Value(value) => {
                 write!(f, "  \"{}\"", value)?;
             }
+            MutationValue::Body { property, body } => {
+                if !property.is_empty() {
+                    write!(f, "  \"{}\"", property)?;
+                }
+                write!(f, "  {}", body)?;
+            }
         }
         Ok(())
     }
@@ -107,34 +181,80 @@ impl fmt::Display for Mutation {
 
 pub(crate) struct Mutator {
     known_params: Conversions,
+    providers: Vec<Box<dyn MutationProvider>>,
     run_all_codes: bool,
+    path_error_status: StatusCode,
+    all_enum_values: bool,
+    combination: CombinationStrategy,
+    seed: u64,
+    security_schemes: BTreeMap<String, openapiv3::SecurityScheme>,
 }
 
 impl Mutator {
     pub(crate) fn new(
         conversions_filename: &Option<String>,
         run_all_codes: bool,
+        path_error_status: StatusCode,
+        all_enum_values: bool,
+        combination: CombinationStrategy,
+        seed: u64,
     ) -> Result<Self, DaedalusError> {
         Ok(Mutator {
             known_params: Conversions::new(conversions_filename)?,
+            providers: Vec::new(),
             run_all_codes,
+            path_error_status,
+            all_enum_values,
+            combination,
+            seed,
+            security_schemes: BTreeMap::new(),
         })
     }
 
+    pub(crate) fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub(crate) fn security_schemes(
+        &mut self,
+        schemes: BTreeMap<String, openapiv3::SecurityScheme>,
+    ) {
+        self.security_schemes = schemes;
+    }
+
+    pub(crate) fn register(&mut self, provider: Box<dyn MutationProvider>) {
+        self.providers.push(provider);
+    }
+
     // TODO: If no mutation is found for one of the required elements, print it out
-    pub(crate) fn mutate<'a>(&self, endpoint: &'a Endpoint) -> Vec<Scenario<'a>> {
-        let mutations = self.mutations_from_mutagen(&endpoint, instructions::mutagens());
-        let query_mutations = self.mutations_from_mutagen_query(&endpoint);
-        //        let body_mutations = self.mutations_from_mutagen_body(&endpoint, instructions::schema_mutagens());
-        self.scenarios_from_mutations(&endpoint, &mutations, &query_mutations)
+    pub(crate) fn mutate(&self, endpoint: &Endpoint) -> Vec<Scenario> {
+        // Endpoints are generated lazily, each one starts its own sequence of random values
+        let key = format!("{} {}", endpoint.crud.to_method_name(), endpoint.path_name);
+        random::reseed(random::seed_for(self.seed, &key));
+        let mut mutations = self.mutations_from_mutagen(endpoint, instructions::mutagens());
+        mutations.extend(credentials::mutate(
+            endpoint,
+            &self.security_schemes,
+            self.known_params.credentials(),
+        ));
+        let mut query_mutations = self.mutations_from_mutagen_params(endpoint);
+        // The conversions file may expect other status codes than ours
+        let expectations = self.known_params.expectations();
+        for mutation in mutations
+            .iter_mut()
+            .chain(query_mutations.iter_mut().flatten())
+        {
+            expectations.apply(&endpoint.path_name, mutation);
+        }
+        self.scenarios_from_mutations(endpoint, &mutations, &query_mutations)
     }
 
-    fn scenarios_from_mutations<'a>(
+    fn scenarios_from_mutations(
         &self,
-        endpoint: &'a Endpoint,
+        endpoint: &Endpoint,
         mutations: &[Mutation],
         query_mutations: &[Vec<Mutation>],
-    ) -> Vec<Scenario<'a>> {
+    ) -> Vec<Scenario> {
         let mut scenarios = vec![];
         let mut query_params: Vec<Vec<&Mutation>> = Vec::new();
         let mut non_query_params: Vec<Vec<&Mutation>> = Vec::new();
@@ -158,74 +278,61 @@ impl Mutator {
             non_query_params.push(group.collect());
         }
 
-        let mut combinations = Vec::new();
-
         // Put everything together
         let mut total = non_query_params;
         total.append(&mut query_params);
 
-        // As per the sorting the first item on each column should be a passing mutation
-        let mut all_good = Vec::new();
-
         // If for instance an endpoint has only one required param but it is not know
         // And we are creating only passing mutations
         // Then we can't create any mutation and this will be empty. Remove these
         total = total.into_iter().filter(|x| !x.is_empty()).collect();
 
-        for endpoint_mutation in &total {
-            all_good.push(endpoint_mutation[0]);
-        }
-
+        // As per the sorting the first item on each column should be a passing mutation
         // If any error here that means we can't combine that category
-        let really_all_good = all_good
+        let really_all_good = total
             .iter()
-            .all(|&m| m.mutagen.expected == StatusCode::OK); //.count();
-
-        combinations.push(all_good);
+            .all(|column| column[0].mutagen.expected.is_success());
 
         // If we can't do anything in one of the categories, there is no point of creating combinations
         // All of them with the same failing guy.
-        if really_all_good {
-            for i in 0..total.len() {
-                //each category
-                for j in 1..total[i].len() {
-                    //each value in a category
-                    //Start from 1 becase we will be choosing the element 0 in inner loop
-                    // If we do 0 here we will choose again and again the top elements.
-                    let mut temp = Vec::new();
-                    for z in 0..total.len() {
-                        // now we transverse the thing to get one from each
-                        if i == z {
-                            temp.push(total[z][j]);
-                        //continue;
-                        } else {
-                            temp.push(total[z][0]);
-                        }
-                    }
-                    combinations.push(temp);
-                }
-            }
+        let combinations = if really_all_good {
+            self.combination
+                .combine(&total, |m| m.mutagen.expected.is_success())
         } else {
             warn!("Could not find a passing scenario for {}. Consider adding information to the conversions file", endpoint.path_name);
-        }
+            vec![total.iter().map(|column| column[0]).collect()]
+        };
 
         // Run all codes means we let 1 error per scenario.
         // We never want more than one error per scenario or we will not know what status code should be out there
         let error_limit = if self.run_all_codes { 1 } else { 0 };
 
+        let mut seen: HashMap<String, usize> = HashMap::new();
         for combination in combinations {
             let erroring = combination
                 .iter()
-                .filter(|&m| m.mutagen.expected != StatusCode::OK)
+                .filter(|&m| !m.mutagen.expected.is_success())
                 .count();
 
             if erroring > error_limit {
                 continue;
             }
 
-            let request = Mutator::request_from_instructions(&combination);
+            let request = match Mutator::request_from_instructions(&combination) {
+                Some(request) => request,
+                None => continue,
+            };
+            let key = Mutator::scenario_key(endpoint, &combination);
+            let count = seen.entry(key.clone()).or_insert(0);
+            *count += 1;
+            let id = format!(
+                "{:08x}",
+                random::fnv_hash(&format!("{}#{}", key, count)) as u32
+            );
+
             let scenario = Scenario::new(
                 endpoint,
+                id,
                 combination.into_iter().cloned().collect(),
                 request,
             );
@@ -233,112 +340,32 @@ impl Mutator {
         }
 
         scenarios
-
-        // Make a copy of the first item of each vector which is per each requestPart or each parameter
-        // let mut first_query_params = Vec::new();
-        // let mut first_nq = Vec::new();
-
-        // for q in &query_params {
-        //     first_query_params.push(vec![*q.get(0).unwrap()]);
-        // }
-
-        // for q in &non_query_params {
-        //     first_nq.push(vec![*q.get(0).unwrap()]);
-        // }
-
-        // // Now we have a set of arrays "first_nq" which all contain 1 element, this should be a passing
-        // // mutation, we put them together with the rest of the query params.
-        // // Later when these guys combine, we will have combinations limited to only on one side the
-        // // one elemnt and on the other many dimensions if available.
-        // // We do the same for the other side and concat.
-
-        // //The whole goal of this implementation is to avoid an all-with-all combination which would be wasteful.
-        // query_params.to_vec().append(&mut first_nq);
-        // non_query_params.to_vec().append(&mut first_query_params);
-
-        // let mut all_things: Vec<Vec<&Mutation>> = non_query_params
-        //     .into_iter()
-        //     .multi_cartesian_product()
-        //     .collect();
-
-        // debug!("number  of all nonquery param {:?}", all_things.len());
-
-        // let mut combination2: Vec<Vec<&Mutation>> =
-        //     query_params.into_iter().multi_cartesian_product().collect();
-
-        // all_things.append(&mut combination2);
-
-        // debug!("number  of all things {:?}", all_things.len());
-        // debug!(" of all things 1 {:?}", all_things[0]);
-        // for combination in all_things {
-        //     let erroring = combination.iter().filter(|&m| m.mutagen.expected != StatusCode::OK).count();
-
-        //     // let mut expected: Vec<StatusCode> =
-        //     //     combination.iter().map(|m| m.mutagen.expected).collect();
-        //     // expected.push(StatusCode::OK); // To avoid matching on a combination with only errors
-        //     // expected.sort();
-        //     // expected.dedup();
-
-        //     // debug!("These are expected {:?}\n", expected);
-        //     // if expected.len() < 3 {
-        //     if erroring <= 1 {
-        //         debug!("new scenario");
-        //         // Get a request from the combination of the things
-        //         let request = Mutator::request_from_instructions(combination.clone());
-        //         // TODO from it create scenario
-        //         let scenario = Scenario::new(
-        //             endpoint.clone(),
-        //             combination.clone().into_iter().cloned().collect(),
-        //             request,
-        //         );
-        //         scenarios.push(scenario);
-        //     }
-        // }
-        // scenarios
     }
 
-    fn request_from_instructions(mutations: &[&Mutation]) -> ScenarioRequest {
+    fn request_from_instructions(mutations: &[&Mutation]) -> Option<ScenarioRequest> {
         let mut request = crate::request::RequestBuilder::new();
+        // The payload says what is sent, mutations from providers may use any request part
         for mutation in mutations {
-            match mutation.mutagen.request_part {
-                RequestPart::ContentType => request.content_type(mutation.value()),
-                RequestPart::Method => request.method(mutation.value()),
-                RequestPart::Path => request.path(mutation.value()),
-                RequestPart::AnyParam => request.query_params(mutation.param_value()),
-                RequestPart::RequiredParam => request.query_params(mutation.param_value()),
-                RequestPart::OptionalParam => request.query_params(mutation.param_value()),
-                _ => unimplemented!(
-                    "We do not know how to mutate this endpoint level item. {:?}",
-                    mutation
-                ),
+            match mutation.payload.clone() {
+                MutationValue::Param(param) => request.param(param),
+                MutationValue::Params(params) => request.params(params),
+                MutationValue::Body { body, .. } => request.body(body),
+                MutationValue::Value(value) => match mutation.mutagen.request_part {
+                    RequestPart::ContentType => request.content_type(value),
+                    RequestPart::Method => request.method(value),
+                    RequestPart::Path => request.path(value),
+                    RequestPart::Body => request.body(value),
+                    _ => {
+                        warn!("Can not send the value of {}, it is ignored", mutation);
+                        &mut request
+                    }
+                },
             };
         }
         request.build()
     }
 
-    // fn mutations_from_mutagen_body(
-    //     &self,
-    //     endpoint: &Endpoint,
-    //     instructions: Vec<MutagenInstruction>,
-    // ) -> Vec<Mutation> {
-    //     let mut mutations = vec![];
-
-    //     let body = endpoint.method.request_body.as_ref();
-    //     if body.is_none() {
-    //         return mutations;
-    //     }
-    //     let schema = body.as_ref().unwrap().clone().to_item().content.get("application/json").as_ref().unwrap().schema;
-
-    //     if schema.is_none() {
-    //         return mutations;
-    //     }
-
-    //     schema.unwrap();
-    //     Vec::new()
-
-    // }
-
-    fn mutations_from_mutagen_query(&self, endpoint: &Endpoint) -> Vec<Vec<Mutation>> {
+    fn mutations_from_mutagen_params(&self, endpoint: &Endpoint) -> Vec<Vec<Mutation>> {
         let mut params = Vec::new();
         if !endpoint.method.optional_parameters().is_empty() {
             params.extend(endpoint.method.optional_parameters());
@@ -350,17 +377,60 @@ impl Mutator {
         params
             .iter()
             .filter_map(|param| {
-                if param.location_string() == "path" {
+                let is_path = param.location_string() == "path";
+                // Without all the path parameters we fill the path from the conversions file directly
+                if (is_path && !endpoint.path_params_declared())
+                    || Mutator::is_ignored_header(param)
+                {
                     None
                 } else {
                     // TODO: avoid heavy processing there inside a loop
                     let known_params = self.known_params.for_path(&endpoint.path_name);
-                    Some(params::mutate(&param, &known_params, self.run_all_codes).variations)
+                    let mut variations = params::mutate(
+                        param,
+                        &known_params,
+                        self.run_all_codes,
+                        self.all_enum_values,
+                    )
+                    .variations;
+                    if is_path {
+                        for variation in variations.iter_mut() {
+                            if !variation.mutagen.expected.is_success() {
+                                variation.mutagen.expected = self.path_error_status;
+                            }
+                        }
+                    }
+                    for provider in &self.providers {
+                        variations.extend(provider.param_mutations(endpoint, param));
+                    }
+                    Some(variations)
                 }
             })
             .collect()
     }
 
+    // Scenarios are identified by their operation and what each mutation changes, but not by
+    // the values used, so random values do not change the ID between runs
+    fn scenario_key(endpoint: &Endpoint, combination: &[&Mutation]) -> String {
+        let mutations: Vec<String> = combination
+            .iter()
+            .map(|mutation| format!("{} {}", mutation.mutagen, mutation.target()))
+            .collect();
+        format!(
+            "{} {} {}",
+            endpoint.crud.to_method_name(),
+            endpoint.path_name,
+            mutations.join(",")
+        )
+    }
+
+    // The spec says header parameters with these names are ignored, they are set elsewhere
+    fn is_ignored_header(param: &openapiv3::Parameter) -> bool {
+        param.location_string() == "header"
+            && ["accept", "content-type", "authorization"]
+                .contains(&param.parameter_data().name.to_lowercase().as_str())
+    }
+
     fn mutations_from_mutagen(
         &self,
         endpoint: &Endpoint,
@@ -386,30 +456,70 @@ impl Mutator {
                     _ => unimplemented!("This content-type mutagen is not implemented!"),
                 },
                 RequestPart::Path => {
-                    if let Some(path) = self.make_path2(&endpoint.path_name, &instruction.mutagen) {
+                    if let Some(path) = self.make_path2(endpoint, &instruction.mutagen) {
                         mutations.push(Mutation::new(instruction, path));
                     }
                 }
+                RequestPart::Body => mutations.extend(self.make_bodies(endpoint, instruction)),
                 _ => unreachable!(),
             }
         }
         mutations
     }
 
-    fn make_path2(&self, path: &str, mutagen: &Mutagen) -> Option<String> {
+    // The valid body goes first, followed by the bodies with one wrong property each
+    fn make_bodies(&self, endpoint: &Endpoint, instruction: MutagenInstruction) -> Vec<Mutation> {
+        let schema = match endpoint.body_schema() {
+            Some(schema) => schema,
+            None => return vec![],
+        };
+        let known_params = self.known_params.for_path(&endpoint.path_name);
+        let body = match schema_value::proper_value(schema, "", &known_params) {
+            Some(body) => body,
+            None => {
+                warn!(
+                    "Could not create a valid body for {} {}. Consider adding information to the conversions file",
+                    endpoint.crud.to_method_name(),
+                    endpoint.path_name
+                );
+                return vec![];
+            }
+        };
+
+        let mut mutations = vec![Mutation::new_body(instruction, "", body.to_string())];
+        for mutation in body_mutation::mutate(schema, &body) {
+            if let Some(instruction) =
+                instructions::schema_mutagen_for(&RequestPart::Body, &mutation.mutagen)
+            {
+                mutations.push(Mutation::new_body(
+                    instruction,
+                    &mutation.property,
+                    mutation.body.to_string(),
+                ));
+            }
+        }
+        mutations
+    }
+
+    fn make_path2(&self, endpoint: &Endpoint, mutagen: &Mutagen) -> Option<String> {
         lazy_static! {
             static ref VARIABLE_FINDER: regex::Regex = regex::Regex::new(r"\{.*?\}").unwrap();
         }
+        let path = &endpoint.path_name;
+        // Declared path parameters are mutated one by one and fill in the template later
+        let with_path_params = path.contains('}') && endpoint.path_params_declared();
         match mutagen {
             Mutagen::PathProper => {
-                if path.contains('}') {
+                if with_path_params {
+                    Some(String::from(path))
+                } else if path.contains('}') {
                     self.known_params.for_path(path).retrieve_known_path(path)
                 } else {
                     Some(String::from(path))
                 }
             }
             Mutagen::PathRandom => {
-                if path.contains('}') {
+                if path.contains('}') && !with_path_params {
                     //let re = regex::Regex::new(r"\{.*?\}").unwrap();
                     Some(
                         VARIABLE_FINDER
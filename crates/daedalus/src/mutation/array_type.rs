@@ -0,0 +1,171 @@
+use crate::known_param::ConversionView;
+use crate::mutation::param_mutation::ParamMutation;
+use crate::mutation::schema_value;
+use crate::mutation::Mutagen;
+use openapi_utils::ReferenceOrExt;
+use openapiv3::{Schema, SchemaKind, Type};
+use serde_json::Value;
+
+// Longer arrays make for unreadable URLs, we do not test those limits
+const MAX_ITEMS: usize = 100;
+
+pub(crate) fn mutate(
+    param: &openapiv3::Parameter,
+    the_type: &openapiv3::ArrayType,
+    known_params: &ConversionView,
+) -> ParamMutation {
+    let mut mutations = ParamMutation::new_param(param);
+    let items_schema: &Schema = the_type.items.to_item_ref();
+    let proper = match schema_value::proper_value(items_schema, "", known_params) {
+        Some(proper) => proper,
+        None => return mutations, // Without one valid item we can't create any list
+    };
+    let items = Items::new(items_schema, &proper, the_type.unique_items);
+
+    let proper_count = std::cmp::max(the_type.min_items.unwrap_or(1), 1)
+        .min(the_type.max_items.unwrap_or(usize::MAX));
+    if let Some(values) = items.take(proper_count) {
+        mutations.push_values(&values, Mutagen::ParamProper);
+    }
+
+    if let Some(min) = the_type.min_items {
+        if min != proper_count && min <= MAX_ITEMS {
+            if let Some(values) = items.take(min) {
+                mutations.push_values(&values, Mutagen::MinimumItems);
+            }
+        }
+        // An empty list would be the same as not sending the parameter
+        if min > 1 && min <= MAX_ITEMS {
+            if let Some(values) = items.take(min - 1) {
+                mutations.push_values(&values, Mutagen::BelowMinimumItems);
+            }
+        }
+    }
+
+    if let Some(max) = the_type.max_items {
+        if max != proper_count && max <= MAX_ITEMS {
+            if let Some(values) = items.take(max) {
+                mutations.push_values(&values, Mutagen::MaximumItems);
+            }
+        }
+        if max < MAX_ITEMS {
+            if let Some(values) = items.take(max + 1) {
+                mutations.push_values(&values, Mutagen::OverMaximumItems);
+            }
+        }
+    }
+
+    // Two equal items, unless two items are already too many
+    if the_type.unique_items && the_type.max_items.unwrap_or(2) >= 2 {
+        let count = std::cmp::max(proper_count, 2);
+        let values = vec![schema_value::value_string(&proper); count];
+        mutations.push_values(&values, Mutagen::DuplicatedItems);
+    }
+
+    if let Some((wrong, mutagen)) = wrong_item(items_schema) {
+        if let Some(mut values) = items.take(proper_count) {
+            values[0] = wrong.to_string();
+            mutations.push_values(&values, mutagen);
+        }
+    }
+
+    mutations
+}
+
+// Valid items to fill in the arrays
+struct Items {
+    distinct: Vec<String>,
+    unique: bool,
+}
+
+impl Items {
+    fn new(schema: &Schema, proper: &Value, unique: bool) -> Self {
+        Items {
+            distinct: distinct_items(schema, proper),
+            unique,
+        }
+    }
+
+    // None when the items need to be unique and we do not know enough different values
+    fn take(&self, count: usize) -> Option<Vec<String>> {
+        if self.unique {
+            if self.distinct.len() < count {
+                None
+            } else {
+                Some(self.distinct[..count].to_vec())
+            }
+        } else {
+            Some(vec![self.distinct[0].clone(); count])
+        }
+    }
+}
+
+// Different values for the items of the array. The first one is always the proper value.
+fn distinct_items(schema: &Schema, proper_value: &Value) -> Vec<String> {
+    let proper = schema_value::value_string(proper_value);
+    let mut items = vec![];
+    let the_type = match &schema.schema_kind {
+        SchemaKind::Type(the_type) => the_type,
+        _ => return vec![proper],
+    };
+    match (the_type, proper_value) {
+        (Type::String(string_type), _) if !string_type.enumeration.is_empty() => {
+            items = string_type.enumeration.clone();
+        }
+        (Type::Integer(integer_type), _) if !integer_type.enumeration.is_empty() => {
+            items = integer_type
+                .enumeration
+                .iter()
+                .map(|item| item.to_string())
+                .collect();
+        }
+        (Type::Integer(integer_type), Value::Number(number)) => {
+            let first = number.as_i64().unwrap_or(1);
+            let step = integer_type.multiple_of.unwrap_or(1).max(1);
+            let max = integer_type.maximum.unwrap_or(i64::MAX);
+            items = (0..MAX_ITEMS as i64)
+                .filter_map(|i| first.checked_add(i.checked_mul(step)?))
+                .take_while(|item| *item <= max)
+                .map(|item| item.to_string())
+                .collect();
+        }
+        (Type::Number(number_type), Value::Number(number)) => {
+            let first = number.as_f64().unwrap_or(1.0);
+            let step = number_type.multiple_of.unwrap_or(1.0);
+            let max = number_type.maximum.unwrap_or(f64::MAX);
+            items = (0..MAX_ITEMS)
+                .map(|i| first + i as f64 * step)
+                .take_while(|item| *item <= max)
+                .map(|item| item.to_string())
+                .collect();
+        }
+        (Type::Boolean {}, _) => items = vec!["true".to_string(), "false".to_string()],
+        // Rotating the characters keeps the length and the characters allowed
+        (Type::String(string_type), Value::String(string))
+            if string_type.format == openapiv3::VariantOrUnknownOrEmpty::Empty =>
+        {
+            let chars: Vec<char> = string.chars().collect();
+            items = (0..chars.len())
+                .map(|i| chars[i..].iter().chain(chars[..i].iter()).collect())
+                .collect();
+        }
+        _ => {}
+    }
+    items.retain(|item| *item != proper);
+    items.insert(0, proper);
+    items
+}
+
+// A value which can not be an item of the array
+fn wrong_item(schema: &Schema) -> Option<(&'static str, Mutagen)> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Integer(_)) | SchemaKind::Type(Type::Number(_)) => {
+            Some(("NotANumber", Mutagen::WrongItemType))
+        }
+        SchemaKind::Type(Type::Boolean {}) => Some(("NotABoolean", Mutagen::WrongItemType)),
+        SchemaKind::Type(Type::String(string_type)) if !string_type.enumeration.is_empty() => {
+            Some(("NotInAnyEnum", Mutagen::NotEnumerationElement))
+        }
+        _ => None,
+    }
+}
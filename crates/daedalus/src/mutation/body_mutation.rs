@@ -0,0 +1,188 @@
+use crate::mutation::schema_value::UNEXPECTED_PROPERTY;
+use crate::mutation::Mutagen;
+use openapi_utils::ReferenceOrExt;
+use openapiv3::{AdditionalProperties, Schema, SchemaKind, Type};
+use serde_json::{Map, Value};
+
+// A valid body where exactly one property has been changed to make it invalid
+pub(crate) struct BodyMutation {
+    pub(crate) property: String,
+    pub(crate) mutagen: Mutagen,
+    pub(crate) body: Value,
+}
+
+impl BodyMutation {
+    fn new(property: &str, mutagen: Mutagen, body: Map<String, Value>) -> Self {
+        BodyMutation {
+            property: property.to_string(),
+            mutagen,
+            body: Value::Object(body),
+        }
+    }
+}
+
+// Starting from a valid body, creates invalid bodies for each of the properties of the schema.
+// Nested objects are also mutated, their properties are named with dots: "address.city"
+pub(crate) fn mutate(schema: &Schema, proper_body: &Value) -> Vec<BodyMutation> {
+    match proper_body {
+        Value::Object(body) => object_mutations(schema, body),
+        _ => vec![],
+    }
+}
+
+fn object_mutations(schema: &Schema, body: &Map<String, Value>) -> Vec<BodyMutation> {
+    let (properties, required, additional_properties) = match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object_type)) => (
+            &object_type.properties,
+            &object_type.required[..],
+            &object_type.additional_properties,
+        ),
+        SchemaKind::Any(any) => (
+            &any.properties,
+            &any.required[..],
+            &any.additional_properties,
+        ),
+        _ => return vec![],
+    };
+
+    let mut mutations = vec![];
+    for (name, property) in properties {
+        let property_schema = property.to_item_ref();
+        let value = match body.get(name) {
+            Some(value) => value,
+            None => continue, // We did not send it, nothing to break
+        };
+
+        let mut mutated = |mutagen: Mutagen, new_value: Option<Value>| {
+            let mut new_body = body.clone();
+            match new_value {
+                Some(new_value) => new_body.insert(name.clone(), new_value),
+                None => new_body.remove(name),
+            };
+            mutations.push(BodyMutation::new(name, mutagen, new_body));
+        };
+
+        if required.contains(name) {
+            mutated(Mutagen::MissingRequiredProperty, None);
+        }
+        if let SchemaKind::Type(the_type) = &property_schema.schema_kind {
+            mutated(Mutagen::WrongType, Some(wrong_type_value(the_type)));
+            for (mutagen, new_value) in restriction_values(the_type) {
+                mutated(mutagen, Some(new_value));
+            }
+        }
+
+        for nested in object_mutations(property_schema, &as_object(value)) {
+            let mut new_body = body.clone();
+            new_body.insert(name.clone(), nested.body);
+            mutations.push(BodyMutation::new(
+                &format!("{}.{}", name, nested.property),
+                nested.mutagen,
+                new_body,
+            ));
+        }
+    }
+
+    if let Some(AdditionalProperties::Any(false)) = additional_properties {
+        let mut new_body = body.clone();
+        new_body.insert(
+            UNEXPECTED_PROPERTY.to_string(),
+            Value::String("Daedalus".to_string()),
+        );
+        mutations.push(BodyMutation::new(
+            UNEXPECTED_PROPERTY,
+            Mutagen::UnexpectedProperty,
+            new_body,
+        ));
+    }
+    mutations
+}
+
+fn as_object(value: &Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object.clone(),
+        _ => Map::new(),
+    }
+}
+
+// Any value which JSON type is not the one of the schema
+fn wrong_type_value(the_type: &Type) -> Value {
+    match the_type {
+        Type::String(_) => Value::from(12345),
+        Type::Integer(_) | Type::Number(_) => Value::from("NotANumber"),
+        Type::Boolean {} => Value::from("NotABoolean"),
+        Type::Array(_) => Value::from("NotAnArray"),
+        Type::Object(_) => Value::from("NotAnObject"),
+    }
+}
+
+fn restriction_values(the_type: &Type) -> Vec<(Mutagen, Value)> {
+    let mut values = vec![];
+    match the_type {
+        Type::String(string_type) => {
+            if !string_type.enumeration.is_empty() {
+                values.push((Mutagen::NotEnumerationElement, Value::from("NotInAnyEnum")));
+            } else {
+                if let Some(min) = string_type.min_length {
+                    if min > 0 {
+                        values.push((
+                            Mutagen::BelowMinimumLength,
+                            Value::from("N".repeat(min - 1)),
+                        ));
+                    }
+                }
+                if let Some(max) = string_type.max_length {
+                    values.push((Mutagen::OverMaximumLength, Value::from("X".repeat(max + 1))));
+                }
+            }
+        }
+        Type::Integer(integer_type) => {
+            if let Some(max) = integer_type.enumeration.iter().max() {
+                values.push((
+                    Mutagen::NotEnumerationElement,
+                    Value::from(max.saturating_add(1)),
+                ));
+            }
+            if let Some(min) = integer_type.minimum {
+                let below = if integer_type.exclusive_minimum {
+                    Some(min)
+                } else {
+                    min.checked_sub(1)
+                };
+                if let Some(below) = below {
+                    values.push((Mutagen::BelowMinimum, Value::from(below)));
+                }
+            }
+            if let Some(max) = integer_type.maximum {
+                let over = if integer_type.exclusive_maximum {
+                    Some(max)
+                } else {
+                    max.checked_add(1)
+                };
+                if let Some(over) = over {
+                    values.push((Mutagen::OverMaximum, Value::from(over)));
+                }
+            }
+        }
+        Type::Number(number_type) => {
+            if let Some(min) = number_type.minimum {
+                let below = if number_type.exclusive_minimum {
+                    min
+                } else {
+                    min - 1.0
+                };
+                values.push((Mutagen::BelowMinimum, Value::from(below)));
+            }
+            if let Some(max) = number_type.maximum {
+                let over = if number_type.exclusive_maximum {
+                    max
+                } else {
+                    max + 1.0
+                };
+                values.push((Mutagen::OverMaximum, Value::from(over)));
+            }
+        }
+        Type::Boolean {} | Type::Array(_) | Type::Object(_) => {}
+    }
+    values
+}
@@ -3,7 +3,7 @@ use crate::mutation::Mutagen;
 use openapi_utils::ParameterExt;
 
 pub(crate) fn mutate(param: &openapiv3::Parameter) -> ParamMutation {
-    let mut mutations = ParamMutation::new_param(&param);
+    let mut mutations = ParamMutation::new_param(param);
     mutations.push("false", Mutagen::ParamProper);
     if param.parameter_data().name != "include_count" {
         mutations.push("NotABool", Mutagen::WrongPattern);
@@ -0,0 +1,203 @@
+use crate::mutation::instructions::{Mutagen, MutagenInstruction, RequestPart};
+use crate::mutation::Mutation;
+use crate::operation::Endpoint;
+use crate::request_param::{ParamLocation, RequestParam};
+use http::StatusCode;
+use openapiv3::{APIKeyLocation, SecurityScheme};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+// Can not be a valid token, key or base64 string
+const MALFORMED: &str = "Daedalus-malformed-credentials";
+const AUTHORIZATION: &str = "Authorization";
+
+// The credentials sent to the operation, out of its security requirements.
+// The first requirement with credentials for all its schemes in the conversions file is used,
+// the failing mutations change the credentials of its first scheme and expect a 401.
+// An operation without requirements is public, it should pass without credentials.
+pub(crate) fn mutate(
+    endpoint: &Endpoint,
+    schemes: &BTreeMap<String, SecurityScheme>,
+    credentials: &BTreeMap<String, String>,
+) -> Vec<Mutation> {
+    let requirements = match &endpoint.method.security {
+        Some(requirements) => requirements,
+        None => return vec![],
+    };
+    let known = requirements
+        .iter()
+        .filter(|requirement| !requirement.is_empty())
+        .find_map(|requirement| {
+            requirement
+                .keys()
+                .map(|name| Some((schemes.get(name)?, credentials.get(name)?.as_str())))
+                .collect::<Option<Vec<_>>>()
+        });
+    // An empty requirement makes the credentials optional, the known ones should pass too
+    if requirements.is_empty()
+        || requirements
+            .iter()
+            .any(|requirement| requirement.is_empty())
+    {
+        let absent = RequestParam::new2(AUTHORIZATION, None).located(ParamLocation::Header);
+        let mut mutations = vec![Mutation::new_param(
+            instruction(Mutagen::None, StatusCode::OK),
+            absent,
+        )];
+        if let Some(known) = known {
+            mutations.push(Mutation::new_params(
+                instruction(Mutagen::CredentialsProper, StatusCode::OK),
+                proper_params(&known),
+            ));
+        }
+        return mutations;
+    }
+
+    let known = match known {
+        Some(known) => known,
+        None => {
+            warn!(
+                "No credentials for the security schemes of {} {}. Consider adding them to the conversions file",
+                endpoint.crud.to_method_name(),
+                endpoint.path_name
+            );
+            return vec![];
+        }
+    };
+
+    let proper = proper_params(&known);
+    let (scheme, credential) = known[0];
+    let with_the_rest = |param: RequestParam| {
+        let mut params = vec![param];
+        params.extend_from_slice(&proper[1..]);
+        params
+    };
+
+    let mut mutations = vec![
+        Mutation::new_params(
+            instruction(Mutagen::CredentialsProper, StatusCode::OK),
+            proper.clone(),
+        ),
+        Mutation::new_params(
+            instruction(Mutagen::None, StatusCode::UNAUTHORIZED),
+            with_the_rest(absent_param(scheme)),
+        ),
+        Mutation::new_params(
+            instruction(Mutagen::MalformedCredentials, StatusCode::UNAUTHORIZED),
+            with_the_rest(malformed_param(scheme)),
+        ),
+    ];
+    // Sending credentials of another scheme the operation accepts would pass
+    let accepted = requirements
+        .iter()
+        .flat_map(|requirement| requirement.keys())
+        .filter_map(|name| schemes.get(name))
+        .any(|accepted| kind(accepted) == wrong_kind(scheme));
+    if let Some(wrong) = wrong_scheme_param(scheme, credential).filter(|_| !accepted) {
+        mutations.push(Mutation::new_params(
+            instruction(Mutagen::WrongScheme, StatusCode::UNAUTHORIZED),
+            with_the_rest(wrong),
+        ));
+    }
+    mutations
+}
+
+fn instruction(mutagen: Mutagen, expected: StatusCode) -> MutagenInstruction {
+    MutagenInstruction::new(RequestPart::Credentials, mutagen, expected)
+}
+
+// How the credentials are sent, "basic", "bearer", "apiKey" or the name of another HTTP scheme.
+// OAuth2 and OpenID Connect tokens are sent as bearer tokens.
+fn kind(scheme: &SecurityScheme) -> String {
+    match scheme {
+        SecurityScheme::APIKey { .. } => String::from("apiKey"),
+        SecurityScheme::HTTP { scheme, .. } => scheme.to_lowercase(),
+        SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => {
+            String::from("bearer")
+        }
+    }
+}
+
+fn wrong_kind(scheme: &SecurityScheme) -> String {
+    match kind(scheme).as_str() {
+        "bearer" => String::from("basic"),
+        _ => String::from("bearer"),
+    }
+}
+
+fn proper_params(known: &[(&SecurityScheme, &str)]) -> Vec<RequestParam> {
+    known
+        .iter()
+        .map(|(scheme, credential)| proper_param(scheme, credential))
+        .collect()
+}
+
+// Basic credentials are written as `user:password` in the conversions file
+fn proper_param(scheme: &SecurityScheme, credential: &str) -> RequestParam {
+    match scheme {
+        SecurityScheme::APIKey { location, name, .. } => {
+            RequestParam::new(name, credential).located(api_key_location(location))
+        }
+        _ => match kind(scheme).as_str() {
+            "basic" => basic(credential),
+            "bearer" => authorization(&format!("Bearer {}", credential)),
+            other => authorization(&format!("{} {}", capitalize(other), credential)),
+        },
+    }
+}
+
+fn absent_param(scheme: &SecurityScheme) -> RequestParam {
+    match scheme {
+        SecurityScheme::APIKey { location, name, .. } => {
+            RequestParam::new2(name, None).located(api_key_location(location))
+        }
+        _ => RequestParam::new2(AUTHORIZATION, None).located(ParamLocation::Header),
+    }
+}
+
+fn malformed_param(scheme: &SecurityScheme) -> RequestParam {
+    match scheme {
+        SecurityScheme::APIKey { location, name, .. } => {
+            RequestParam::new(name, MALFORMED).located(api_key_location(location))
+        }
+        _ => authorization(&format!("{} {}", capitalize(&kind(scheme)), MALFORMED)),
+    }
+}
+
+// Bearer tokens are sent as Basic credentials and the rest as bearer tokens
+fn wrong_scheme_param(scheme: &SecurityScheme, credential: &str) -> Option<RequestParam> {
+    match scheme {
+        // The key would be sent in the same header
+        SecurityScheme::APIKey { location, name, .. }
+            if *location == APIKeyLocation::Header && name.eq_ignore_ascii_case(AUTHORIZATION) =>
+        {
+            None
+        }
+        _ if kind(scheme) == "bearer" => Some(basic(credential)),
+        _ => Some(authorization(&format!("Bearer {}", credential))),
+    }
+}
+
+fn basic(credential: &str) -> RequestParam {
+    authorization(&format!("Basic {}", base64::encode(credential)))
+}
+
+fn authorization(value: &str) -> RequestParam {
+    RequestParam::new(AUTHORIZATION, value).located(ParamLocation::Header)
+}
+
+fn api_key_location(location: &APIKeyLocation) -> ParamLocation {
+    match location {
+        APIKeyLocation::Query => ParamLocation::Query,
+        APIKeyLocation::Header => ParamLocation::Header,
+        APIKeyLocation::Cookie => ParamLocation::Cookie,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
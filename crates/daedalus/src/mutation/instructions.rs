@@ -1,31 +1,85 @@
 use crate::request_param::RequestParam;
 use http::StatusCode;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, PartialEq, Clone)]
+/// The kind of change done to a part of the request
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Mutagen {
+    /// Proper values for the whole endpoint
     EndpointProperValues,
     // Path mutagen
+    /// The path with proper values
     PathProper,
+    /// A path with random values
     PathRandom, // No format, so should not be checked
-    // TODO: PathImproper, merge it with params
 
     // Query param mutagen
+    /// A valid value for the parameter
     ParamProper,
+    /// A value not following the format or pattern
     WrongPattern,
+    /// The parameter is not sent
     None,
+    /// A string shorter than the minimum length
     BelowMinimumLength,
+    /// A string of just the minimum length
     MinimumLength,
+    /// A string of just the maximum length
     MaximumLength,
+    /// A string longer than the maximum length
     OverMaximumLength,
+    /// A number below the minimum
     BelowMinimum,
+    /// Just the minimum number
     Minimum,
+    /// Just the maximum number
     Maximum,
+    /// A number over the maximum
     OverMaximum,
+    /// A number which is not a multiple of `multipleOf`
+    NotMultipleOf,
+    /// NaN or Infinity
+    NotFinite,
+    /// An array with less items than the minimum
+    BelowMinimumItems,
+    /// An array with just the minimum number of items
+    MinimumItems,
+    /// An array with just the maximum number of items
+    MaximumItems,
+    /// An array with more items than the maximum
+    OverMaximumItems,
+    /// An array with repeated items when they should be unique
+    DuplicatedItems,
+    /// An array with an item of the wrong type
+    WrongItemType,
+    /// A value of the enumeration
     EnumerationElement,
+    /// A value outside of the enumeration
     NotEnumerationElement,
+    /// A fixed value
     Value(String),
+    /// A fixed parameter
     StaticParam(RequestParam),
+    // Body mutagen
+    /// A valid request body
+    BodyProper,
+    /// A body missing a required property
+    MissingRequiredProperty,
+    /// A value of the wrong type
+    WrongType,
+    /// A property which is not allowed
+    UnexpectedProperty,
+    // Credentials mutagen
+    /// Valid credentials for the security schemes of the operation
+    CredentialsProper,
+    /// Credentials of the right scheme which can not be valid
+    MalformedCredentials,
+    /// Credentials of a scheme the operation does not accept
+    WrongScheme,
+    /// Created by a `MutationProvider`, described by the string
+    Custom(String),
     // EmptyString,
     // HugelyLongString,
 }
@@ -47,55 +101,95 @@ impl fmt::Display for Mutagen {
             Mutagen::Minimum => f.write_str("just the minimum value for this number"),
             Mutagen::Maximum => f.write_str("just the maximum value for this number"),
             Mutagen::OverMaximum => f.write_str("over the maximum value for this number"),
+            Mutagen::NotMultipleOf => f.write_str("not a multiple of the required number"),
+            Mutagen::NotFinite => f.write_str("not a finite number"),
+            Mutagen::BelowMinimumItems => f.write_str("below the minimum number of items"),
+            Mutagen::MinimumItems => f.write_str("just the minimum number of items"),
+            Mutagen::MaximumItems => f.write_str("just the maximum number of items"),
+            Mutagen::OverMaximumItems => f.write_str("over the maximum number of items"),
+            Mutagen::DuplicatedItems => f.write_str("contains duplicated items"),
+            Mutagen::WrongItemType => f.write_str("contains an item of the wrong type"),
             Mutagen::EnumerationElement => f.write_str("a possible value of the enumeration"),
             Mutagen::NotEnumerationElement => {
                 f.write_str("outside the possible values of the enumeration")
             }
             Mutagen::Value(string) => write!(f, "contains the value {}", string),
             Mutagen::StaticParam(param) => write!(f, "contains the request parameter {:?}", param),
+            Mutagen::BodyProper => f.write_str("contains a valid document"),
+            Mutagen::MissingRequiredProperty => f.write_str("misses a required property"),
+            Mutagen::WrongType => f.write_str("contains a value of the wrong type"),
+            Mutagen::UnexpectedProperty => f.write_str("contains an unexpected property"),
+            Mutagen::CredentialsProper => f.write_str("are valid"),
+            Mutagen::MalformedCredentials => f.write_str("are malformed"),
+            Mutagen::WrongScheme => f.write_str("use the wrong authentication scheme"),
+            Mutagen::Custom(description) => f.write_str(description),
             // Mutagen::EmptyString => write!(f, "contains an empty string"),
             // Mutagen::HugelyLongString => write!(f, "contains an very long string"),
         }
     }
 }
-#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord)]
+/// The part of the request a mutagen is applied to
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RequestPart {
+    /// The path of the URL
     Path,
+    /// A parameter in the path
+    PathParam,
+    /// Any parameter
     AnyParam, // Params can also be headers, cookies and paths
+    /// A required parameter
     RequiredParam,
+    /// An optional parameter
     OptionalParam,
+    /// The whole endpoint
     Endpoint,
+    /// The HTTP method
     Method,
+    /// The Content-Type header
     ContentType,
+    /// The request body
+    Body,
+    /// The credentials required by the security schemes
+    Credentials,
 }
 
 impl fmt::Display for RequestPart {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             RequestPart::Path => f.write_str("The path"),
-            RequestPart::AnyParam => f.write_str("Query parameter"),
+            RequestPart::PathParam => f.write_str("Path parameter"),
+            RequestPart::AnyParam => f.write_str("Parameter"),
             RequestPart::OptionalParam => f.write_str("Optional parameter"),
             RequestPart::RequiredParam => f.write_str("Required parameter"),
             RequestPart::Endpoint => f.write_str("The endpoint"),
             RequestPart::Method => f.write_str("The HTTP method"),
             RequestPart::ContentType => f.write_str("The Content-Type"),
+            RequestPart::Body => f.write_str("The request body"),
+            RequestPart::Credentials => f.write_str("The credentials"),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+/// A mutagen applied to a part of the request and the status code we expect for it
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct MutagenInstruction {
+    /// The change done
     pub mutagen: Mutagen,
+    /// Where the change is done
     pub request_part: RequestPart,
+    /// The status code expected
+    #[serde(with = "crate::status_code")]
     pub expected: StatusCode,
 }
 
 impl MutagenInstruction {
-    fn new(tuple: (RequestPart, Mutagen, StatusCode)) -> Self {
+    /// The mutagen applied to the part of the request, expecting the status code
+    pub fn new(request_part: RequestPart, mutagen: Mutagen, expected: StatusCode) -> Self {
         MutagenInstruction {
-            request_part: tuple.0,
-            mutagen: tuple.1,
-            expected: tuple.2,
+            request_part,
+            mutagen,
+            expected,
         }
     }
     fn new_with_list(tuple: (RequestPart, StatusCode, Vec<Mutagen>)) -> Vec<Self> {
@@ -125,6 +219,16 @@ pub fn schema_mutagen(mutagen: &Mutagen) -> Vec<MutagenInstruction> {
         .collect()
 }
 
+// The instruction for a mutagen applied to a specific part of the request
+pub fn schema_mutagen_for(
+    request_part: &RequestPart,
+    mutagen: &Mutagen,
+) -> Option<MutagenInstruction> {
+    schema_mutagens().into_iter().find(|instruction| {
+        instruction.request_part == *request_part && instruction.mutagen == *mutagen
+    })
+}
+
 // TODO: allow multiple possible returns types because different possible valid implementations
 fn schema_mutagens() -> Vec<MutagenInstruction> {
     vec![
@@ -137,6 +241,8 @@ fn schema_mutagens() -> Vec<MutagenInstruction> {
                 Mutagen::MaximumLength,
                 Mutagen::Minimum,
                 Mutagen::Maximum,
+                Mutagen::MinimumItems,
+                Mutagen::MaximumItems,
                 Mutagen::EnumerationElement,
             ],
         ),
@@ -159,7 +265,50 @@ fn schema_mutagens() -> Vec<MutagenInstruction> {
                 Mutagen::OverMaximumLength,
                 Mutagen::BelowMinimum,
                 Mutagen::OverMaximum,
+                Mutagen::NotMultipleOf,
+                Mutagen::NotFinite,
+                Mutagen::BelowMinimumItems,
+                Mutagen::OverMaximumItems,
+                Mutagen::DuplicatedItems,
+                Mutagen::WrongItemType,
+                Mutagen::MissingRequiredProperty,
+                Mutagen::WrongType,
+                Mutagen::UnexpectedProperty,
+                Mutagen::NotEnumerationElement,
+            ],
+        ),
+        (
+            RequestPart::PathParam,
+            StatusCode::OK,
+            vec![Mutagen::ParamProper],
+        ),
+        // The Mutator can be configured to expect a different code
+        (
+            RequestPart::PathParam,
+            StatusCode::NOT_FOUND,
+            vec![
+                Mutagen::WrongPattern,
+                Mutagen::BelowMinimumLength,
+                Mutagen::OverMaximumLength,
+                Mutagen::BelowMinimum,
+                Mutagen::OverMaximum,
+                Mutagen::NotMultipleOf,
+                Mutagen::NotFinite,
+                Mutagen::NotEnumerationElement,
+            ],
+        ),
+        (
+            RequestPart::Body,
+            StatusCode::UNPROCESSABLE_ENTITY,
+            vec![
+                Mutagen::MissingRequiredProperty,
+                Mutagen::WrongType,
+                Mutagen::BelowMinimumLength,
+                Mutagen::OverMaximumLength,
+                Mutagen::BelowMinimum,
+                Mutagen::OverMaximum,
                 Mutagen::NotEnumerationElement,
+                Mutagen::UnexpectedProperty,
             ],
         ),
     ]
@@ -186,11 +335,6 @@ pub fn mutagens() -> Vec<MutagenInstruction> {
             Mutagen::PathRandom,
             StatusCode::NOT_FOUND,
         ),
-        // (
-        //     RequestPart::Path,
-        //     Mutagen::PathImproperFormat,
-        //     StatusCode::UNPROCESSABLE_ENTITY,
-        // ),
         (
             RequestPart::ContentType,
             Mutagen::Value(String::from("application/json")),
@@ -201,6 +345,7 @@ pub fn mutagens() -> Vec<MutagenInstruction> {
             Mutagen::Value(String::from("application/jason")),
             StatusCode::NOT_ACCEPTABLE,
         ),
+        (RequestPart::Body, Mutagen::BodyProper, StatusCode::OK),
         (
             RequestPart::Endpoint,
             Mutagen::StaticParam(RequestParam::new("trusmis", "mimi")),
@@ -209,6 +354,8 @@ pub fn mutagens() -> Vec<MutagenInstruction> {
         // TODO: Additional uknown HTTP headers
     ]
     .into_iter()
-    .map(MutagenInstruction::new)
+    .map(|(request_part, mutagen, expected)| {
+        MutagenInstruction::new(request_part, mutagen, expected)
+    })
     .collect()
 }
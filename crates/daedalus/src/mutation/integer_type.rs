@@ -5,8 +5,32 @@ use openapi_utils::{IntegerTypeExt, ParameterExt};
 pub(crate) fn mutate(
     param: &openapiv3::Parameter,
     the_type: &openapiv3::IntegerType,
+    all_enum_values: bool,
 ) -> ParamMutation {
-    let mut mutations = ParamMutation::new_param(&param);
+    let mut mutations = ParamMutation::new_param(param);
+
+    if !the_type.enumeration.is_empty() {
+        mutations.push(&the_type.enumeration[0].to_string(), Mutagen::ParamProper);
+        // Check each value is managed
+        if all_enum_values {
+            for element in the_type.enumeration.iter().skip(1) {
+                mutations.push(&element.to_string(), Mutagen::EnumerationElement);
+            }
+        }
+        // Past the maximum, or before the minimum if the maximum is the largest integer
+        let enumeration = &the_type.enumeration;
+        let outside = enumeration
+            .iter()
+            .max()
+            .and_then(|max| max.checked_add(1))
+            .or_else(|| enumeration.iter().min().and_then(|min| min.checked_sub(1)));
+        if let Some(outside) = outside {
+            mutations.push(&outside.to_string(), Mutagen::NotEnumerationElement);
+        }
+        mutations.push("NotAnInteger", Mutagen::WrongPattern);
+        return mutations;
+    }
+
     let (min, max) = the_type.min_max();
     let avg = max / 2; //(min + max) / 2; if max is max of i64 this will overflow
 
@@ -0,0 +1,80 @@
+use crate::mutation::param_mutation::ParamMutation;
+use crate::mutation::schema_value;
+use crate::mutation::Mutagen;
+use openapiv3::{NumberFormat, VariantOrUnknownOrEmpty};
+
+// Bigger than anything a float can hold
+const OUT_OF_FLOAT_RANGE: f64 = 1e39;
+
+pub(crate) fn mutate(
+    param: &openapiv3::Parameter,
+    the_type: &openapiv3::NumberType,
+) -> ParamMutation {
+    let mut mutations = ParamMutation::new_param(param);
+    let proper = schema_value::number_value(the_type);
+    mutations.push(&proper.to_string(), Mutagen::ParamProper);
+
+    let is_float = the_type.format == VariantOrUnknownOrEmpty::Item(NumberFormat::Float);
+
+    match the_type.minimum {
+        // The minimum itself is not valid when exclusive
+        Some(min) if the_type.exclusive_minimum => {
+            mutations.push(&min.to_string(), Mutagen::BelowMinimum)
+        }
+        Some(min) => {
+            mutations.push(&min.to_string(), Mutagen::Minimum);
+            mutations.push(&(min - 1.0).to_string(), Mutagen::BelowMinimum);
+        }
+        None if is_float => {
+            mutations.push(&(-OUT_OF_FLOAT_RANGE).to_string(), Mutagen::BelowMinimum)
+        }
+        None => {}
+    }
+
+    match the_type.maximum {
+        Some(max) if the_type.exclusive_maximum => {
+            mutations.push(&max.to_string(), Mutagen::OverMaximum)
+        }
+        Some(max) => {
+            mutations.push(&max.to_string(), Mutagen::Maximum);
+            mutations.push(&(max + 1.0).to_string(), Mutagen::OverMaximum);
+        }
+        None if is_float => mutations.push(&OUT_OF_FLOAT_RANGE.to_string(), Mutagen::OverMaximum),
+        None => {}
+    }
+
+    // Only one error per scenario, the value must stay within the limits
+    if let Some(multiple) = the_type.multiple_of {
+        if multiple > 0.0 {
+            let not_multiple = [proper + multiple / 2.0, proper - multiple / 2.0]
+                .iter()
+                .copied()
+                .find(|value| in_range(the_type, *value));
+            if let Some(value) = not_multiple {
+                mutations.push(&value.to_string(), Mutagen::NotMultipleOf);
+            }
+        }
+    }
+
+    // JSON does not have these numbers but many parsers accept them
+    mutations.push("NaN", Mutagen::NotFinite);
+    mutations.push("Infinity", Mutagen::NotFinite);
+    mutations.push("-Infinity", Mutagen::NotFinite);
+    mutations.push("NotANumber", Mutagen::WrongPattern);
+
+    mutations
+}
+
+fn in_range(the_type: &openapiv3::NumberType, value: f64) -> bool {
+    let above_minimum = match the_type.minimum {
+        Some(min) if the_type.exclusive_minimum => value > min,
+        Some(min) => value >= min,
+        None => true,
+    };
+    let below_maximum = match the_type.maximum {
+        Some(max) if the_type.exclusive_maximum => value < max,
+        Some(max) => value <= max,
+        None => true,
+    };
+    above_minimum && below_maximum
+}
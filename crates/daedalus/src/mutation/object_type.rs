@@ -0,0 +1,65 @@
+use crate::known_param::ConversionView;
+use crate::mutation::param_mutation::ParamMutation;
+use crate::mutation::schema_value;
+use crate::mutation::Mutagen;
+use openapi_utils::ReferenceOrExt;
+use openapiv3::{AdditionalProperties, Schema, SchemaKind, Type};
+use serde_json::Value;
+
+pub(crate) fn mutate(
+    param: &openapiv3::Parameter,
+    the_type: &openapiv3::ObjectType,
+    known_params: &ConversionView,
+) -> ParamMutation {
+    let mut mutations = ParamMutation::new_param(param);
+    let proper: Vec<(String, String)> = match schema_value::proper_object(the_type, known_params) {
+        Some(Value::Object(object)) => object
+            .iter()
+            .map(|(key, value)| (key.clone(), schema_value::value_string(value)))
+            .collect(),
+        _ => return mutations,
+    };
+    mutations.push_object(&proper, Mutagen::ParamProper);
+
+    for (name, property) in &the_type.properties {
+        let position = match proper.iter().position(|(key, _)| key == name) {
+            Some(position) => position,
+            None => continue, // We did not send it, nothing to break
+        };
+        if the_type.required.contains(name) {
+            let mut missing = proper.clone();
+            missing.remove(position);
+            mutations.push_object(&missing, Mutagen::MissingRequiredProperty);
+        }
+        if let Some((wrong, mutagen)) = wrong_value(property.to_item_ref()) {
+            let mut wrong_typed = proper.clone();
+            wrong_typed[position].1 = wrong.to_string();
+            mutations.push_object(&wrong_typed, mutagen);
+        }
+    }
+
+    if let Some(AdditionalProperties::Any(false)) = the_type.additional_properties {
+        let mut unexpected = proper.clone();
+        unexpected.push((
+            schema_value::UNEXPECTED_PROPERTY.to_string(),
+            "Daedalus".to_string(),
+        ));
+        mutations.push_object(&unexpected, Mutagen::UnexpectedProperty);
+    }
+
+    mutations
+}
+
+// In a query string everything is a string, only some types can be wrong
+fn wrong_value(schema: &Schema) -> Option<(&'static str, Mutagen)> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Integer(_)) | SchemaKind::Type(Type::Number(_)) => {
+            Some(("NotANumber", Mutagen::WrongType))
+        }
+        SchemaKind::Type(Type::Boolean {}) => Some(("NotABoolean", Mutagen::WrongType)),
+        SchemaKind::Type(Type::String(string_type)) if !string_type.enumeration.is_empty() => {
+            Some(("NotInAnyEnum", Mutagen::NotEnumerationElement))
+        }
+        _ => None,
+    }
+}
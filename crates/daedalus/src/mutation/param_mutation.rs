@@ -1,7 +1,11 @@
-use crate::mutation::instructions::{schema_mutagen, RequestPart};
+use crate::mutation::instructions::{
+    schema_mutagen, schema_mutagen_for, MutagenInstruction, RequestPart,
+};
 use crate::mutation::{Mutagen, Mutation};
-use crate::request_param::RequestParam;
+use crate::request_param::{ParamLocation, RequestParam};
 use openapi_utils::ParameterExt;
+use openapiv3::{Parameter, QueryStyle};
+
 pub(crate) struct ParamMutation {
     pub(crate) variations: Vec<Mutation>,
     pub(crate) param: openapiv3::Parameter,
@@ -15,11 +19,113 @@ impl ParamMutation {
         }
     }
     pub(crate) fn push(&mut self, value: &str, mutagen: Mutagen) {
-        let instruction = schema_mutagen(&mutagen)[0].clone();
-        let param = RequestParam::new(&self.param.parameter_data().name, value);
+        let instruction = match self.instruction(&mutagen) {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        let param = RequestParam::new(&self.param.parameter_data().name, value)
+            .located(ParamLocation::from(&self.param));
         self.variations
             .push(Mutation::new_param(instruction, param));
     }
+    // Arrays are serialized following the style and explode of the parameter
+    pub(crate) fn push_values(&mut self, values: &[String], mutagen: Mutagen) {
+        let instruction = match self.instruction(&mutagen) {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        let name = self.param.parameter_data().name.clone();
+        let location = ParamLocation::from(&self.param);
+        let (delimiter, explode) = self.array_style();
+
+        let params = if !explode {
+            vec![RequestParam::new(&name, &values.join(delimiter)).located(location)]
+        } else if values.is_empty() {
+            vec![RequestParam::new2(&name, None).located(location)]
+        } else {
+            values
+                .iter()
+                .map(|value| RequestParam::new(&name, value).located(location))
+                .collect()
+        };
+        self.variations
+            .push(Mutation::new_params(instruction, params));
+    }
+    // Objects are serialized following the style and explode of the parameter
+    pub(crate) fn push_object(&mut self, properties: &[(String, String)], mutagen: Mutagen) {
+        let instruction = match self.instruction(&mutagen) {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        let name = self.param.parameter_data().name.clone();
+        let location = ParamLocation::from(&self.param);
+        let explode = self.param.parameter_data().explode;
+
+        let params: Vec<RequestParam> = match &self.param {
+            Parameter::Query {
+                style: QueryStyle::DeepObject,
+                ..
+            } => properties
+                .iter()
+                .map(|(key, value)| RequestParam::new(&format!("{}[{}]", name, key), value))
+                .collect(),
+            Parameter::Query { .. } | Parameter::Cookie { .. } if explode.unwrap_or(true) => {
+                properties
+                    .iter()
+                    .map(|(key, value)| RequestParam::new(key, value))
+                    .collect()
+            }
+            Parameter::Header { .. } | Parameter::Path { .. } if explode.unwrap_or(false) => {
+                let pairs: Vec<String> = properties
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect();
+                vec![RequestParam::new(&name, &pairs.join(","))]
+            }
+            _ => {
+                let flat: Vec<&str> = properties
+                    .iter()
+                    .flat_map(|(key, value)| vec![key.as_str(), value.as_str()])
+                    .collect();
+                vec![RequestParam::new(&name, &flat.join(","))]
+            }
+        };
+        let params = if params.is_empty() {
+            vec![RequestParam::new2(&name, None)]
+        } else {
+            params
+        };
+        let params = params
+            .into_iter()
+            .map(|param| param.located(location))
+            .collect();
+        self.variations
+            .push(Mutation::new_params(instruction, params));
+    }
+    fn instruction(&self, mutagen: &Mutagen) -> Option<MutagenInstruction> {
+        if ParamLocation::from(&self.param) == ParamLocation::Path {
+            // Some mutations do not make sense for a path, ex. the minimum value for a number
+            schema_mutagen_for(&RequestPart::PathParam, mutagen)
+        } else {
+            schema_mutagen(mutagen).first().cloned()
+        }
+    }
+    // Only form style can repeat the parameter, it does so by default
+    fn array_style(&self) -> (&str, bool) {
+        let explode = self.param.parameter_data().explode;
+        match &self.param {
+            Parameter::Query {
+                style: QueryStyle::SpaceDelimited,
+                ..
+            } => (" ", explode.unwrap_or(false)),
+            Parameter::Query {
+                style: QueryStyle::PipeDelimited,
+                ..
+            } => ("|", explode.unwrap_or(false)),
+            Parameter::Query { .. } | Parameter::Cookie { .. } => (",", explode.unwrap_or(true)),
+            _ => (",", false),
+        }
+    }
     pub(crate) fn push_multiple(
         &mut self,
         value: Option<String>,
@@ -33,7 +139,8 @@ impl ParamMutation {
                 (instruction.request_part == RequestPart::RequiredParam) == required
             })
             .unwrap();
-        let param = RequestParam::new2(&self.param.parameter_data().name, value);
+        let param = RequestParam::new2(&self.param.parameter_data().name, value)
+            .located(ParamLocation::from(&self.param));
         self.variations
             .push(Mutation::new_param(instruction, param));
         // for instruction in instructions {
@@ -1,46 +1,122 @@
 use crate::known_param::ConversionView;
+use crate::mutation::array_type;
 use crate::mutation::bool_type;
 use crate::mutation::integer_type;
+use crate::mutation::number_type;
+use crate::mutation::object_type;
 use crate::mutation::param_mutation::ParamMutation;
+use crate::mutation::schema_value;
 use crate::mutation::string_type;
 use crate::mutation::Mutagen;
-use openapi_utils::{ParameterDataExt, ParameterExt};
-use openapiv3::Type;
+use crate::request_param::ParamLocation;
+use openapi_utils::{ParameterDataExt, ParameterExt, ReferenceOrExt};
+use openapiv3::{ParameterData, ParameterSchemaOrContent, ReferenceOr, Type};
+use serde_json::Value;
+
+// Spaces, quotes and commas are not allowed in cookie values
+const MALFORMED_COOKIE: &str = "Daedalus \"malformed\", cookie";
 
 /// TODO: How to make sure we generate for all the Mutagens?
 pub(crate) fn mutate(
     param: &openapiv3::Parameter,
     known_params: &ConversionView,
     run_all_scenarios: bool,
+    all_enum_values: bool,
 ) -> ParamMutation {
     let data = param.parameter_data();
     let mut mutations = ParamMutation::new_param(param);
+    let location = ParamLocation::from(param);
 
-    mutations.push_multiple(None, Mutagen::None, param.parameter_data().required);
+    // A path can not miss one of its parts
+    if location != ParamLocation::Path {
+        mutations.push_multiple(None, Mutagen::None, param.parameter_data().required);
+    }
 
-    if let Some(param_value) = known_params.param_value(&data.name) {
-        mutations.push(&param_value, Mutagen::ParamProper);
-        if !run_all_scenarios {
-            return mutations; // As soon as we have something that can give 200, give up trying to make more scenarios
-        }
+    // Conversions are preferred, then the examples and default documented in the spec
+    let known_proper = if let Some(param_value) = known_params.param_value(&data.name) {
+        mutations.push(param_value, Mutagen::ParamProper);
+        true
+    } else if let Some(example) = example_value(data) {
+        push_value(&mut mutations, &example, Mutagen::ParamProper)
+    } else {
+        false
+    };
+    if known_proper && !run_all_scenarios {
+        return mutations; // As soon as we have something that can give 200, give up trying to make more scenarios
+    }
+
+    if location == ParamLocation::Cookie {
+        mutations.push(MALFORMED_COOKIE, Mutagen::WrongPattern);
     }
 
     if !data.is_type_defined() {
         //return empty, we can't mutate anything without types
         return ParamMutation::new_param(param);
     } else {
-        let typed_mutations = match data.get_type() {
-            Type::Boolean {} => bool_type::mutate(&param),
-            Type::Integer(the_type) => integer_type::mutate(&param, the_type),
-            //Type::Number { .. } => {
-            //     RequestParam::new(&data.name, "NotAnIntegerhahahaha")
-            // },
-            // Type::Array { .. } => RequestParam::new(&data.name, "notAnArray"),
-            // Type::Object { .. } => RequestParam::new(&data.name, "notAnObject"),
-            Type::String(the_type) => string_type::mutate(&param, the_type),
-            _ => ParamMutation::new_param(param), //unimplemented!("Not this umproper"),
+        let mut typed_mutations = match data.get_type() {
+            Type::Boolean {} => bool_type::mutate(param),
+            Type::Integer(the_type) => integer_type::mutate(param, the_type, all_enum_values),
+            Type::Number(the_type) => number_type::mutate(param, the_type),
+            Type::Array(the_type) => array_type::mutate(param, the_type, known_params),
+            Type::Object(the_type) => object_type::mutate(param, the_type, known_params),
+            Type::String(the_type) => string_type::mutate(param, the_type, all_enum_values),
         };
+        if location == ParamLocation::Path {
+            // Made up values would point to resources which do not exist, only conversions can pass
+            typed_mutations
+                .variations
+                .retain(|mutation| !mutation.mutagen.expected.is_success());
+        } else if known_proper {
+            // We already have a proper value, no need for a made up one
+            typed_mutations
+                .variations
+                .retain(|mutation| mutation.mutagen.mutagen != Mutagen::ParamProper);
+        }
         mutations.extend(typed_mutations);
     }
     mutations
 }
+
+// The first of the example, examples, schema example or schema default of the parameter
+fn example_value(data: &ParameterData) -> Option<Value> {
+    if let Some(example) = &data.example {
+        return Some(example.clone());
+    }
+    let from_examples = data.examples.values().find_map(|example| match example {
+        ReferenceOr::Item(example) => example.value.clone(),
+        ReferenceOr::Reference { .. } => None,
+    });
+    if from_examples.is_some() {
+        return from_examples;
+    }
+    match &data.format {
+        ParameterSchemaOrContent::Schema(schema) => {
+            let schema_data = &schema.to_item_ref().schema_data;
+            schema_data
+                .example
+                .clone()
+                .or_else(|| schema_data.default.clone())
+        }
+        _ => None,
+    }
+}
+
+// Pushes the value serialized as the parameter requires, false if it can not be sent
+fn push_value(mutations: &mut ParamMutation, value: &Value, mutagen: Mutagen) -> bool {
+    match value {
+        Value::Null => return false,
+        Value::Array(items) => {
+            let values: Vec<String> = items.iter().map(schema_value::value_string).collect();
+            mutations.push_values(&values, mutagen);
+        }
+        Value::Object(object) => {
+            let properties: Vec<(String, String)> = object
+                .iter()
+                .map(|(key, value)| (key.clone(), schema_value::value_string(value)))
+                .collect();
+            mutations.push_object(&properties, mutagen);
+        }
+        other => mutations.push(&schema_value::value_string(other), mutagen),
+    }
+    true
+}
@@ -0,0 +1,82 @@
+use crate::random;
+use rand::Rng;
+use tracing::debug;
+
+// How many strings we try to create before giving up on a pattern
+const ATTEMPTS: usize = 100;
+// Maximum times `*`, `+` and `{n,}` repeat, long enough for most lengths
+const MAX_REPEAT: u32 = 16;
+
+// A string matching the pattern within the length limits.
+// None when the pattern is not supported or we could not find a valid string.
+pub(crate) fn matching(
+    pattern: &str,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+) -> Option<String> {
+    let validator = regex::Regex::new(pattern).ok()?;
+    let generator = match rand_regex::Regex::compile(without_anchors(pattern), MAX_REPEAT) {
+        Ok(generator) => generator,
+        Err(error) => {
+            debug!(
+                "Can not create strings for the pattern {}: {:?}",
+                pattern, error
+            );
+            return None;
+        }
+    };
+
+    let mut fallback = None;
+    for _ in 0..ATTEMPTS {
+        let candidate: String = random::with_rng(|rng| rng.sample(&generator));
+        if validator.is_match(&candidate) && within_length(&candidate, min_length, max_length) {
+            // Non ASCII strings can not be sent as headers, we prefer ASCII ones
+            if candidate.is_ascii() {
+                return Some(candidate);
+            }
+            fallback.get_or_insert(candidate);
+        }
+    }
+    fallback
+}
+
+// A string not matching the pattern, within the length limits if possible so only the pattern is wrong
+pub(crate) fn not_matching(
+    pattern: &str,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+) -> Option<String> {
+    let validator = regex::Regex::new(pattern).ok()?;
+    let min = min_length.unwrap_or(0);
+    let candidates: Vec<String> = vec![
+        "!".repeat(std::cmp::max(min, 1)),
+        "-".repeat(std::cmp::max(min, 1)),
+        " ".repeat(std::cmp::max(min, 1)),
+        String::from("NotMatchingPattern"),
+        String::new(),
+    ];
+    let not_matching: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| !validator.is_match(candidate))
+        .collect();
+    not_matching
+        .iter()
+        .find(|candidate| within_length(candidate, min_length, max_length))
+        .or_else(|| not_matching.first())
+        .cloned()
+}
+
+fn within_length(string: &str, min_length: Option<usize>, max_length: Option<usize>) -> bool {
+    let length = string.chars().count();
+    length >= min_length.unwrap_or(0) && length <= max_length.unwrap_or(usize::MAX)
+}
+
+// The generator does not support anchors, what it creates is already the full string
+fn without_anchors(pattern: &str) -> &str {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    if pattern.ends_with('$') && !pattern.ends_with("\\$") {
+        &pattern[..pattern.len() - 1]
+    } else {
+        pattern
+    }
+}
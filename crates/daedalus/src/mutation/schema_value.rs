@@ -0,0 +1,255 @@
+use crate::known_param::ConversionView;
+use crate::mutation::pattern;
+use crate::mutation::string_type;
+use crate::random;
+use openapi_utils::ReferenceOrExt;
+use openapiv3::{ReferenceOr, Schema, SchemaKind, Type};
+use serde_json::{Map, Number, Value};
+
+// Schemas may be recursive, we stop going down at this depth
+const MAX_DEPTH: usize = 10;
+const PLAIN_STRING: &str = "Daedalus";
+// Not in any schema, unless it is written on purpose
+pub(crate) const UNEXPECTED_PROPERTY: &str = "daedalusUnexpectedProperty";
+
+// Creates a JSON value which is valid according to the schema.
+// Values in the conversions file are preferred, then the example and default of the schema,
+// only when there is none of those we create a value out of the restrictions of the schema.
+// None means we do not know how to create a valid value.
+pub(crate) fn proper_value(
+    schema: &Schema,
+    name: &str,
+    known_params: &ConversionView,
+) -> Option<Value> {
+    value_at_depth(schema, name, known_params, 0)
+}
+
+// A valid object with the properties of the object type
+pub(crate) fn proper_object(
+    object_type: &openapiv3::ObjectType,
+    known_params: &ConversionView,
+) -> Option<Value> {
+    object_value(
+        object_type.properties.iter(),
+        &object_type.required,
+        known_params,
+        0,
+    )
+}
+
+fn value_at_depth(
+    schema: &Schema,
+    name: &str,
+    known_params: &ConversionView,
+    depth: usize,
+) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    if let SchemaKind::Type(the_type) = &schema.schema_kind {
+        if let Some(known) = known_params.param_value(name) {
+            if let Some(value) = known_value(the_type, known) {
+                return Some(value);
+            }
+        }
+    }
+
+    if let Some(example) = &schema.schema_data.example {
+        return Some(example.clone());
+    }
+    if let Some(default) = &schema.schema_data.default {
+        return Some(default.clone());
+    }
+
+    match &schema.schema_kind {
+        SchemaKind::Type(the_type) => typed_value(the_type, name, known_params, depth),
+        SchemaKind::OneOf { one_of } => one_of
+            .iter()
+            .find_map(|schema| value_at_depth(schema.to_item_ref(), name, known_params, depth + 1)),
+        SchemaKind::AnyOf { any_of } => any_of
+            .iter()
+            .find_map(|schema| value_at_depth(schema.to_item_ref(), name, known_params, depth + 1)),
+        SchemaKind::AllOf { all_of } => {
+            let mut merged = Map::new();
+            for schema in all_of {
+                match value_at_depth(schema.to_item_ref(), name, known_params, depth + 1)? {
+                    Value::Object(properties) => merged.extend(properties),
+                    other => return Some(other),
+                }
+            }
+            Some(Value::Object(merged))
+        }
+        SchemaKind::Any(any) => {
+            if any.properties.is_empty() {
+                None
+            } else {
+                object_value(any.properties.iter(), &[], known_params, depth)
+            }
+        }
+    }
+}
+
+// The conversions file only knows about strings, we make them into the JSON type of the schema
+fn known_value(the_type: &Type, known: &str) -> Option<Value> {
+    match the_type {
+        Type::String(_) => Some(Value::String(known.to_string())),
+        Type::Integer(_) => known.parse::<i64>().ok().map(Value::from),
+        Type::Number(_) => known
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number),
+        Type::Boolean {} => known.parse::<bool>().ok().map(Value::Bool),
+        _ => None,
+    }
+}
+
+fn typed_value(
+    the_type: &Type,
+    name: &str,
+    known_params: &ConversionView,
+    depth: usize,
+) -> Option<Value> {
+    match the_type {
+        Type::String(string_type) => string_value(string_type).map(Value::String),
+        Type::Integer(integer_type) => Some(Value::from(integer_value(integer_type))),
+        Type::Number(number_type) => Number::from_f64(number_value(number_type)).map(Value::Number),
+        Type::Boolean {} => Some(Value::Bool(true)),
+        Type::Array(array_type) => {
+            let item = value_at_depth(
+                array_type.items.to_item_ref(),
+                name,
+                known_params,
+                depth + 1,
+            )?;
+            let count = std::cmp::max(array_type.min_items.unwrap_or(1), 1)
+                .min(array_type.max_items.unwrap_or(usize::MAX));
+            Some(Value::Array(vec![item; count]))
+        }
+        Type::Object(object_type) => object_value(
+            object_type.properties.iter(),
+            &object_type.required,
+            known_params,
+            depth,
+        ),
+    }
+}
+
+fn object_value<'a>(
+    properties: impl Iterator<Item = (&'a String, &'a ReferenceOr<Box<Schema>>)>,
+    required: &[String],
+    known_params: &ConversionView,
+    depth: usize,
+) -> Option<Value> {
+    let mut object = Map::new();
+    for (name, property) in properties {
+        let schema = property.to_item_ref();
+        // The server owns these, clients should not send them
+        if schema.schema_data.read_only {
+            continue;
+        }
+        match value_at_depth(schema, name, known_params, depth + 1) {
+            Some(value) => {
+                object.insert(name.clone(), value);
+            }
+            None => {
+                if required.contains(name) {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(Value::Object(object))
+}
+
+pub(crate) fn string_value(string_type: &openapiv3::StringType) -> Option<String> {
+    if let Some(first) = string_type.enumeration.first() {
+        return Some(first.clone());
+    }
+    if let Some(regex) = &string_type.pattern {
+        return pattern::matching(regex, string_type.min_length, string_type.max_length);
+    }
+
+    match &string_type.format {
+        openapiv3::VariantOrUnknownOrEmpty::Empty => {
+            Some(sized_string(string_type.min_length, string_type.max_length))
+        }
+        // A new resource can have any uuid
+        openapiv3::VariantOrUnknownOrEmpty::Unknown(format) if format == "uuid" => {
+            Some(random::uuid())
+        }
+        _ => string_type::proper_string_from_format(string_type),
+    }
+}
+
+fn sized_string(min_length: Option<usize>, max_length: Option<usize>) -> String {
+    let length = std::cmp::max(PLAIN_STRING.len(), min_length.unwrap_or(0))
+        .min(max_length.unwrap_or(usize::MAX));
+    PLAIN_STRING.chars().cycle().take(length).collect()
+}
+
+pub(crate) fn integer_value(integer_type: &openapiv3::IntegerType) -> i64 {
+    if let Some(first) = integer_type.enumeration.first() {
+        return *first;
+    }
+
+    let mut value = 1;
+    if let Some(min) = integer_type.minimum {
+        let min = if integer_type.exclusive_minimum {
+            min.saturating_add(1)
+        } else {
+            min
+        };
+        value = std::cmp::max(value, min);
+    }
+    if let Some(max) = integer_type.maximum {
+        let max = if integer_type.exclusive_maximum {
+            max.saturating_sub(1)
+        } else {
+            max
+        };
+        value = std::cmp::min(value, max);
+    }
+    if let Some(multiple) = integer_type.multiple_of {
+        if multiple > 0 {
+            value = value.saturating_add((multiple - value.rem_euclid(multiple)) % multiple);
+        }
+    }
+    value
+}
+
+pub(crate) fn number_value(number_type: &openapiv3::NumberType) -> f64 {
+    if let Some(first) = number_type.enumeration.first() {
+        return *first;
+    }
+
+    let mut value = 1.0;
+    if let Some(min) = number_type.minimum {
+        if value < min || (number_type.exclusive_minimum && value <= min) {
+            value = min + 1.0;
+        }
+    }
+    if let Some(max) = number_type.maximum {
+        if value > max || (number_type.exclusive_maximum && value >= max) {
+            value = match number_type.minimum {
+                Some(min) => (min + max) / 2.0,
+                None => max - 1.0,
+            };
+        }
+    }
+    if let Some(multiple) = number_type.multiple_of {
+        if multiple > 0.0 {
+            value = (value / multiple).ceil() * multiple;
+        }
+    }
+    value
+}
+
+// Parameters are sent as text, strings without their JSON quotes
+pub(crate) fn value_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        other => other.to_string(),
+    }
+}
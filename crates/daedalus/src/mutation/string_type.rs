@@ -1,13 +1,15 @@
 use crate::mutation::param_mutation::ParamMutation;
+use crate::mutation::pattern;
 use crate::mutation::Mutagen;
+use crate::random;
 use chrono::prelude::*;
 
-// TODO String type has "pattern"
 pub(crate) fn mutate(
     param: &openapiv3::Parameter,
     string_type: &openapiv3::StringType,
+    all_enum_values: bool,
 ) -> ParamMutation {
-    let mut mutations = ParamMutation::new_param(&param);
+    let mut mutations = ParamMutation::new_param(param);
 
     // First variations we can always produce, then we will create variations which depend on factors
     // mutations.push("", Mutagen::EmptyString);
@@ -19,15 +21,25 @@ pub(crate) fn mutate(
         if !a_value.chars().all(char::is_uppercase) {
             mutations.push(&a_value.to_uppercase(), Mutagen::NotEnumerationElement);
         }
-        // Below if we want to check each value is managed
-        // for element in &string_type.enumeration {
-        //     mutations.push(&element, Mutagen::EnumerationElement);
-        //     if !element.chars().all(char::is_uppercase) {
-        //         mutations.push(&element.to_uppercase(), Mutagen::NotEnumerationElement);
-        //     }
-        // }
+        // Check each value is managed
+        if all_enum_values {
+            for element in &string_type.enumeration {
+                mutations.push(element, Mutagen::EnumerationElement);
+            }
+        }
         mutations.push("", Mutagen::NotEnumerationElement);
         mutations.push("NotInAnyEnum", Mutagen::NotEnumerationElement);
+    } else if let Some(regex) = &string_type.pattern {
+        let (min, max) = (string_type.min_length, string_type.max_length);
+        if let Some(proper_value) = pattern::matching(regex, min, max) {
+            mutations.push(&proper_value, Mutagen::ParamProper);
+        }
+        if let Some(wrong_value) = pattern::not_matching(regex, min, max) {
+            mutations.push(&wrong_value, Mutagen::WrongPattern);
+        }
+        if let Some(max) = max {
+            mutations.push(&"X".repeat(max + 1), Mutagen::OverMaximumLength);
+        }
     } else if string_type.format == openapiv3::VariantOrUnknownOrEmpty::Empty {
         if let Some(min) = string_type.min_length {
             if min > 1 {
@@ -52,45 +64,65 @@ pub(crate) fn mutate(
         if let Some(proper_value) = proper_string_from_format(string_type) {
             mutations.push(&proper_value, Mutagen::ParamProper);
         }
-        mutations.push("NotValidValueForFormat", Mutagen::WrongPattern);
+        if let Some(wrong_value) = improper_string_from_format(string_type) {
+            mutations.push(&wrong_value, Mutagen::WrongPattern);
+        }
     }
 
     mutations
 }
 
-fn proper_string_from_format(string_type: &openapiv3::StringType) -> Option<String> {
+// openapiv3 only knows some formats, the rest are unknown. We treat all of them the same.
+fn format_name(string_type: &openapiv3::StringType) -> Option<&str> {
     match &string_type.format {
         openapiv3::VariantOrUnknownOrEmpty::Item(string_format) => match string_format {
-            openapiv3::StringFormat::Date => Some(format!("{:?}", Utc.ymd(2019, 11, 28))),
-            openapiv3::StringFormat::DateTime => {
-                let date_time = Utc.ymd(2019, 11, 28).and_hms(12, 0, 9);
-                Some(format!("{:?}", date_time))
-            }
-            _ => unimplemented!("String format not supported"),
+            openapiv3::StringFormat::Date => Some("date"),
+            openapiv3::StringFormat::DateTime => Some("date-time"),
+            openapiv3::StringFormat::Password => Some("password"),
+            openapiv3::StringFormat::Byte => Some("byte"),
+            openapiv3::StringFormat::Binary => Some("binary"),
         },
-        openapiv3::VariantOrUnknownOrEmpty::Unknown(string) => {
-            if string == "uuid" {
-                // TODO: Use Conversions here and do proper thing
-                // We can't do a random uuid, as it will fail. None says we did not create a valid param
-                // TODO: This will end up creating a mutation without this
-                // But if the parameter was required, we want to not be able to create anything
-                None
-            // let uuid = uuid::Uuid::new_v4();
-            // RequestParam::new(&name, &format!("{:?}", uuid))
-            // This is a bug in the openapiv3 library
-            //https://github.com/glademiller/openapiv3/blob/master/src/schema.rs#L203
-            } else if string == "date-time" {
-                let date_time = Utc.ymd(2020, 1, 13).and_hms(12, 0, 9);
-                Some(format!("{:?}", date_time))
-            } else if string == "mdsol_uri" {
-                // A format used by Medidata. Includes UUID. Use conversions
-                None
-            } else {
-                Some(String::from("PLAIN_STRING_UNKNOWN"))
-                // TODO plain string
-                // unimplemented!("No plain string support")
-            }
-        }
+        openapiv3::VariantOrUnknownOrEmpty::Unknown(string) => Some(string),
         openapiv3::VariantOrUnknownOrEmpty::Empty => None,
     }
 }
+
+pub(crate) fn proper_string_from_format(string_type: &openapiv3::StringType) -> Option<String> {
+    match format_name(string_type)? {
+        "date" => Some(Utc.ymd(2019, 11, 28).format("%Y-%m-%d").to_string()),
+        "date-time" => {
+            let date_time = Utc.ymd(2019, 11, 28).and_hms(12, 0, 9);
+            Some(format!("{:?}", date_time))
+        }
+        "email" => Some(String::from("daedalus@example.com")),
+        "uri" | "url" => Some(String::from("https://example.com/daedalus")),
+        "hostname" => Some(String::from("daedalus.example.com")),
+        "ipv4" => Some(String::from("192.168.1.1")),
+        "ipv6" => Some(String::from("2001:db8::1")),
+        "byte" => Some(String::from("RGFlZGFsdXM=")), // "Daedalus" in base64
+        "binary" => Some(String::from("Daedalus")),
+        "password" => Some(String::from("Daedalus-Passw0rd")),
+        "uuid" => Some(random::uuid()),
+        // A format used by Medidata. Includes UUID. Use conversions
+        "mdsol_uri" => None,
+        // Formats are extensible, for the ones we do not know we try a plain string
+        _ => Some(String::from("PLAIN_STRING_UNKNOWN")),
+    }
+}
+
+// None when any string is valid for the format or we do not know what makes it invalid.
+// The values are safe in an URI, they are sent as path parameters too
+pub(crate) fn improper_string_from_format(string_type: &openapiv3::StringType) -> Option<String> {
+    match format_name(string_type)? {
+        "date" => Some(String::from("2019-13-45")),
+        "date-time" => Some(String::from("2019-11-28T25:61:00Z")),
+        "email" => Some(String::from("daedalus.example.com")),
+        "uri" | "url" => Some(String::from("not:a:uri")),
+        "hostname" => Some(String::from("-daedalus_.example.com")),
+        "ipv4" => Some(String::from("256.256.256.256")),
+        "ipv6" => Some(String::from("2001:db8:::1:zz")),
+        "byte" => Some(String::from("Not-base64!")),
+        "uuid" | "mdsol_uri" => Some(String::from("NotValidValueForFormat")),
+        _ => None,
+    }
+}
@@ -0,0 +1,269 @@
+use crate::error::DaedalusError;
+use crate::reference;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+const SCHEMAS: &str = "/components/schemas";
+
+// Keys whose values are maps of names, ex. the names of the properties. Their keys are not keywords.
+const NAMED_MAPS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "schemas",
+    "parameters",
+    "responses",
+    "requestBodies",
+    "headers",
+    "content",
+    "examples",
+    "encoding",
+    "links",
+    "callbacks",
+    "securitySchemes",
+    "variables",
+    "paths",
+    "webhooks",
+    "mapping",
+];
+
+// Keys whose values are data, ex. the example of a schema. They are not converted.
+const DATA: &[&str] = &["example", "default", "enum", "const", "value"];
+
+pub(crate) fn is_openapi_31(document: &Value) -> bool {
+    match document.get("openapi") {
+        Some(Value::String(version)) => version.starts_with("3.1"),
+        _ => false,
+    }
+}
+
+// Rewrites an OpenAPI 3.1 document as the 3.0 document with the same meaning, when there is one.
+// JSON Schema keywords without an equivalent in 3.0 are ignored.
+pub(crate) fn convert(document: Value, filename: &str) -> Result<Value, DaedalusError> {
+    let mut definitions = Definitions {
+        root: &document,
+        filename,
+        stack: Vec::new(),
+        names: HashMap::new(),
+        schemas: Mapping::new(),
+        in_parameters: false,
+    };
+    let mut converted = definitions.hoist(&document)?;
+    let schemas = std::mem::take(&mut definitions.schemas);
+    reference::insert_schemas(&mut converted, SCHEMAS, schemas);
+    if let Value::Mapping(map) = &mut converted {
+        map.insert(key("openapi"), Value::String("3.0.3".to_string()));
+        // Paths are optional in 3.1
+        if !map.contains_key(&key("paths")) {
+            map.insert(key("paths"), Value::Mapping(Mapping::new()));
+        }
+        map.remove(&key("webhooks"));
+    }
+    convert_value(&mut converted, false);
+    Ok(converted)
+}
+
+// 3.0 schemas have no `$defs`, the schemas referenced in them are moved to the schemas of the document
+// and referenced from there, so they can be recursive
+struct Definitions<'a> {
+    root: &'a Value,
+    filename: &'a str,
+    // The references being followed, to find cycles
+    stack: Vec<String>,
+    // The names given to the moved schemas, by their reference
+    names: HashMap<String, String>,
+    schemas: Mapping,
+    // The schemas of parameters are not dereferenced after reading, they are inlined
+    in_parameters: bool,
+}
+
+impl Definitions<'_> {
+    fn hoist(&mut self, value: &Value) -> Result<Value, DaedalusError> {
+        match value {
+            Value::Mapping(map) => {
+                if let Some(Value::String(reference)) = map.get(&key("$ref")) {
+                    if let Some(pointer) = reference
+                        .strip_prefix('#')
+                        .filter(|pointer| pointer.contains("/$defs/"))
+                    {
+                        return self.hoist_reference(reference, pointer);
+                    }
+                }
+                let mut converted = Mapping::new();
+                for (name, value) in map {
+                    let hoisted = match name.as_str() {
+                        Some("$defs") => continue,
+                        Some("parameters") => {
+                            let in_parameters = std::mem::replace(&mut self.in_parameters, true);
+                            let hoisted = self.hoist(value);
+                            self.in_parameters = in_parameters;
+                            hoisted?
+                        }
+                        _ => self.hoist(value)?,
+                    };
+                    converted.insert(name.clone(), hoisted);
+                }
+                Ok(Value::Mapping(converted))
+            }
+            Value::Sequence(list) => list
+                .iter()
+                .map(|value| self.hoist(value))
+                .collect::<Result<_, _>>()
+                .map(Value::Sequence),
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn hoist_reference(&mut self, reference: &str, pointer: &str) -> Result<Value, DaedalusError> {
+        if let Some(name) = self.names.get(reference).filter(|_| !self.in_parameters) {
+            return Ok(schema_reference(name));
+        }
+        if let Some(start) = self
+            .stack
+            .iter()
+            .position(|resolving| resolving == reference)
+        {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(reference.to_string());
+            return Err(DaedalusError::ReferenceCycle {
+                reference: reference.to_string(),
+                filename: self.filename.to_string(),
+                cycle: cycle.join(" -> "),
+            });
+        }
+        let root = self.root;
+        let target =
+            reference::find(root, pointer).ok_or_else(|| DaedalusError::UnresolvedReference {
+                reference: reference.to_string(),
+                filename: self.filename.to_string(),
+                target: self.filename.to_string(),
+                pointer: pointer.to_string(),
+            })?;
+
+        // A reference to another reference is followed until a schema with content is found,
+        // only a chain of references without any content is a cycle.
+        // Parameters can not point to other schemas, recursive parameter schemas are cycles too.
+        if self.in_parameters || target.get("$ref").is_some() {
+            self.stack.push(reference.to_string());
+            let hoisted = self.hoist(target);
+            self.stack.pop();
+            return hoisted;
+        }
+        let base = pointer.rsplit('/').next().unwrap_or_default();
+        let base = base.replace("~1", "/").replace("~0", "~");
+        let name = reference::unused_name(&base, |name| {
+            self.names.values().any(|taken| taken == name)
+                || reference::find(root, &format!("{}/{}", SCHEMAS, name)).is_some()
+        });
+        self.names.insert(reference.to_string(), name.clone());
+        let stack = std::mem::take(&mut self.stack);
+        let hoisted = self.hoist(target);
+        self.stack = stack;
+        self.schemas.insert(key(&name), hoisted?);
+        Ok(schema_reference(&name))
+    }
+}
+
+fn schema_reference(name: &str) -> Value {
+    reference::local_reference(&format!("{}/{}", SCHEMAS, name))
+}
+
+fn convert_value(value: &mut Value, named: bool) {
+    match value {
+        Value::Mapping(map) => {
+            for (name, value) in map.iter_mut() {
+                let name = if named { None } else { name.as_str() };
+                let data = matches!(name, Some(name) if DATA.contains(&name))
+                    || (name == Some("examples") && value.is_sequence());
+                if !data {
+                    let child_named = matches!(name, Some(name) if NAMED_MAPS.contains(&name));
+                    convert_value(value, child_named);
+                }
+            }
+            if !named {
+                convert_schema(map);
+            }
+        }
+        Value::Sequence(list) => {
+            for value in list {
+                convert_value(value, false);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn convert_schema(schema: &mut Mapping) {
+    // `type: [string, "null"]` is a nullable string, several types are several schemas
+    match schema.get(&key("type")).cloned() {
+        Some(Value::Sequence(types)) => {
+            schema.remove(&key("type"));
+            let (nulls, types): (Vec<Value>, Vec<Value>) = types
+                .into_iter()
+                .partition(|the_type| the_type.as_str() == Some("null"));
+            if !nulls.is_empty() {
+                schema.insert(key("nullable"), Value::Bool(true));
+            }
+            match types.len() {
+                0 => {}
+                1 => {
+                    schema.insert(key("type"), types[0].clone());
+                }
+                _ => {
+                    let any_of = types
+                        .into_iter()
+                        .map(|the_type| {
+                            let mut single = Mapping::new();
+                            single.insert(key("type"), the_type);
+                            Value::Mapping(single)
+                        })
+                        .collect();
+                    schema.insert(key("anyOf"), Value::Sequence(any_of));
+                }
+            }
+        }
+        Some(Value::String(the_type)) if the_type == "null" => {
+            schema.remove(&key("type"));
+            schema.insert(key("nullable"), Value::Bool(true));
+        }
+        _ => {}
+    }
+
+    if let Some(constant) = schema.remove(&key("const")) {
+        // A constant alone is a schema of its type in 3.1
+        let the_type = match &constant {
+            Value::String(_) => Some("string"),
+            Value::Bool(_) => Some("boolean"),
+            Value::Number(number) if number.is_f64() => Some("number"),
+            Value::Number(_) => Some("integer"),
+            _ => None,
+        };
+        if let Some(the_type) = the_type {
+            if !schema.contains_key(&key("type")) && !schema.contains_key(&key("anyOf")) {
+                schema.insert(key("type"), key(the_type));
+            }
+        }
+        schema.insert(key("enum"), Value::Sequence(vec![constant]));
+    }
+
+    if let Some(Value::Sequence(examples)) = schema.get(&key("examples")).cloned() {
+        schema.remove(&key("examples"));
+        if let Some(example) = examples.into_iter().next() {
+            schema.insert(key("example"), example);
+        }
+    }
+
+    // Exclusive limits are numbers in 3.1 and flags of the limits in 3.0
+    for (exclusive, limit) in &[
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(Value::Number(number)) = schema.get(&key(exclusive)).cloned() {
+            schema.insert(key(limit), Value::Number(number));
+            schema.insert(key(exclusive), Value::Bool(true));
+        }
+    }
+}
+
+fn key(name: &str) -> Value {
+    Value::String(name.to_string())
+}
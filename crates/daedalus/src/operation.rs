@@ -1,10 +1,20 @@
+use http::StatusCode;
+use openapi_utils::{OperationExt, ParameterExt, ReferenceOrExt};
+
+/// The kind of operation of an endpoint
 #[derive(PartialEq, Clone, Debug)]
 pub enum Crud {
+    /// GET of a collection
     Index,
+    /// POST
     Create,
+    /// GET of a single resource
     Show,
+    /// PUT
     Update,
+    /// DELETE
     Delete,
+    /// PATCH
     Patch,
 }
 
@@ -12,6 +22,7 @@ impl Crud {
     // TODO This is kind of a hack to make mutator work with this piece
     // Probably we want to clean this up or just remove the concept of Crud
     // as it is only useful to tell the difference between index and show
+    /// The HTTP method of the operation
     pub fn to_method_name(&self) -> &str {
         match self {
             Crud::Index => "GET",
@@ -24,11 +35,18 @@ impl Crud {
     }
 }
 
+/// An operation of the openapi file
 #[derive(Clone, Debug)]
 pub struct Endpoint {
+    /// The kind of operation
     pub crud: Crud,
+    /// The path, with its variables, ex. `/users/{uuid}`
     pub path_name: String,
+    /// The openapi operation, with the parameters of its path
     pub method: openapiv3::Operation,
+    /// The scheme and host of the closest servers of the operation, ex. `https://api.example.com`.
+    /// None when the openapi file has no servers or their URLs are relative.
+    pub server_origin: Option<String>,
 }
 
 impl Endpoint {
@@ -37,60 +55,95 @@ impl Endpoint {
             crud,
             path_name: path_name.to_string(),
             method,
+            server_origin: None,
         }
     }
 
-    // TODO: Do not limit to GET
     pub(crate) fn new_supported(path_name: &str, methods: &openapiv3::PathItem) -> Vec<Self> {
-        Self::create_supported_endpoint(path_name, methods)
-            .into_iter()
-            .filter(|x| x.crud == Crud::Show || x.crud == Crud::Index)
-            .collect()
-    }
+        let get_crud = if Endpoint::url_with_variable(path_name) {
+            Crud::Show
+        } else {
+            Crud::Index
+        };
 
-    // TODO Return Vec which may be empty instead
-    fn create_supported_endpoint(path_name: &str, methods: &openapiv3::PathItem) -> Vec<Self> {
-        let mut vec = Vec::new();
-        if Endpoint::url_with_variable(path_name) {
-            let maybe_get = methods
-                .get
-                .clone()
-                .map(|get| Endpoint::new(Crud::Show, path_name, get));
-            let maybe_put = methods
-                .put
-                .clone()
-                .map(|put| Endpoint::new(Crud::Update, path_name, put));
-            let maybe_patch = methods
-                .patch
-                .clone()
-                .map(|patch| Endpoint::new(Crud::Patch, path_name, patch));
-            let maybe_delete = methods
-                .delete
-                .clone()
-                .map(|delete| Endpoint::new(Crud::Delete, path_name, delete));
+        vec![
+            (get_crud, &methods.get),
+            (Crud::Create, &methods.post),
+            (Crud::Update, &methods.put),
+            (Crud::Patch, &methods.patch),
+            (Crud::Delete, &methods.delete),
+        ]
+        .into_iter()
+        .filter_map(|(crud, operation)| {
+            operation.clone().map(|mut operation| {
+                Endpoint::merge_parameters(&mut operation, &methods.parameters);
+                Endpoint::new(crud, path_name, operation)
+            })
+        })
+        .collect()
+    }
 
-            vec.push(maybe_get);
-            vec.push(maybe_put);
-            vec.push(maybe_patch);
-            vec.push(maybe_delete);
-            vec.retain(|c| c.is_some());
-            vec.into_iter().map(|o| o.unwrap()).collect()
-        } else {
-            let maybe_get = methods
-                .get
-                .clone()
-                .map(|get| Endpoint::new(Crud::Index, path_name, get));
-            let maybe_post = methods
-                .put
-                .clone()
-                .map(|post| Endpoint::new(Crud::Create, path_name, post));
-            vec.push(maybe_get);
-            vec.push(maybe_post);
-            vec.retain(|c| c.is_some());
-            vec.into_iter().map(|o| o.unwrap()).collect()
+    // Parameters can be defined for all the operations of a path
+    // The operation can override them using the same name and location
+    fn merge_parameters(
+        operation: &mut openapiv3::Operation,
+        path_parameters: &[openapiv3::ReferenceOr<openapiv3::Parameter>],
+    ) {
+        for path_parameter in path_parameters {
+            let overridden = operation.parameters.iter().any(|parameter| {
+                let (parameter, path_parameter) =
+                    (parameter.to_item_ref(), path_parameter.to_item_ref());
+                parameter.parameter_data().name == path_parameter.parameter_data().name
+                    && parameter.location_string() == path_parameter.location_string()
+            });
+            if !overridden {
+                operation.parameters.push(path_parameter.clone());
+            }
         }
     }
 
+    // True when all the variables in the path have a path parameter describing them
+    pub(crate) fn path_params_declared(&self) -> bool {
+        let declared: Vec<String> = self
+            .method
+            .parameters
+            .iter()
+            .map(|parameter| parameter.to_item_ref())
+            .filter(|parameter| parameter.location_string() == "path")
+            .map(|parameter| parameter.parameter_data().name.clone())
+            .collect();
+        self.path_name
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split('}').next())
+            .all(|variable| declared.iter().any(|name| name == variable))
+    }
+
+    // The status code a passing scenario should receive.
+    // This is the first success code documented for the operation, ex. 201 for creations
+    // or 204 for deletions. If the operation documents none we still expect a 200.
+    pub(crate) fn success_status(&self) -> StatusCode {
+        (200..300)
+            .find(|code| self.method.response(*code).is_some())
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .unwrap_or(StatusCode::OK)
+    }
+
+    // The schema of the JSON document this operation accepts, if any
+    pub(crate) fn body_schema(&self) -> Option<&openapiv3::Schema> {
+        self.method
+            .request_body
+            .as_ref()?
+            .to_item_ref()
+            .content
+            .iter()
+            .find(|(media_type, _)| media_type.contains("json"))?
+            .1
+            .schema
+            .as_ref()
+            .map(|schema| schema.to_item_ref())
+    }
+
     fn url_with_variable(path_name: &str) -> bool {
         path_name.contains('}')
     }
@@ -0,0 +1,14 @@
+use crate::mutation::Mutation;
+use crate::operation::Endpoint;
+
+/// Creates extra mutations for the parameters of the endpoints.
+///
+/// Implement it for checks specific to your services and register it with
+/// [`Generator::register`](crate::Generator::register).
+/// Each mutation is combined with proper values for the rest of the request,
+/// so a failing mutation becomes a scenario with just that error.
+pub trait MutationProvider {
+    /// Mutations for `param`, one of the parameters of `endpoint`.
+    /// Build them with [`Mutation::for_param`](crate::Mutation::for_param).
+    fn param_mutations(&self, endpoint: &Endpoint, param: &openapiv3::Parameter) -> Vec<Mutation>;
+}
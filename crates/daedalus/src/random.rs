@@ -0,0 +1,38 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+// All the random values of a run come from this generator, so the seed reproduces the run.
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+// Each endpoint gets its own sequence out of the seed, so filtering endpoints does not change the others
+pub(crate) fn seed_for(seed: u64, key: &str) -> u64 {
+    seed ^ fnv_hash(key)
+}
+
+// FNV-1a, unlike the std hasher it gives the same result in every Rust version
+pub(crate) fn fnv_hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub(crate) fn reseed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+pub(crate) fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+// A random version 4 uuid
+pub(crate) fn uuid() -> String {
+    let bytes = with_rng(|rng| rng.gen::<[u8; 16]>());
+    uuid::Builder::from_bytes(bytes)
+        .set_variant(uuid::Variant::RFC4122)
+        .set_version(uuid::Version::Random)
+        .build()
+        .to_string()
+}
@@ -0,0 +1,325 @@
+use crate::error::DaedalusError;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// Replaces the references to other files, ex. `./paths/users.yaml`, by their contents.
+// The local references of the main file are kept, they are resolved later with the rest of the spec.
+// Schemas of other files, ex. `./schemas/user.yaml#/User`, are moved to the schemas of the main file
+// and referenced from there, so they can be recursive.
+// References in the other files are relative to the file they are in.
+pub(crate) struct Resolver {
+    root: PathBuf,
+    documents: HashMap<PathBuf, Value>,
+    // The references being resolved, to find cycles
+    stack: Vec<String>,
+    // Where the schemas of the main file are, `/components/schemas` or `/definitions` in Swagger 2.0
+    schemas_pointer: String,
+    // The names given to the schemas of other files, by their reference
+    names: HashMap<String, String>,
+    schemas: Mapping,
+    // The schemas of parameters are not dereferenced after reading, they are inlined
+    in_parameters: bool,
+}
+
+// What is under a key of the document
+enum Position {
+    Schema,
+    Schemas,
+    Parameters,
+    Other,
+}
+
+impl Resolver {
+    pub(crate) fn new(filename: &str) -> Self {
+        Resolver {
+            root: canonical(Path::new(&shellexpand::tilde(filename).into_owned())),
+            documents: HashMap::new(),
+            stack: Vec::new(),
+            schemas_pointer: String::new(),
+            names: HashMap::new(),
+            schemas: Mapping::new(),
+            in_parameters: false,
+        }
+    }
+
+    pub(crate) fn resolve(&mut self) -> Result<Value, DaedalusError> {
+        let root = self.root.clone();
+        let document = self.document(&root)?.clone();
+        self.schemas_pointer = if document.get("swagger").is_some() {
+            String::from("/definitions")
+        } else {
+            String::from("/components/schemas")
+        };
+        let mut resolved = self.resolve_value(&document, &root, false)?;
+        self.insert_schemas(&mut resolved);
+        Ok(resolved)
+    }
+
+    fn resolve_value(
+        &mut self,
+        value: &Value,
+        file: &Path,
+        schema: bool,
+    ) -> Result<Value, DaedalusError> {
+        match value {
+            Value::Mapping(map) => match map.get(&Value::String("$ref".to_string())) {
+                Some(Value::String(reference)) => {
+                    self.resolve_reference(value, reference, file, schema)
+                }
+                _ => {
+                    let mut resolved = Mapping::new();
+                    for (key, value) in map {
+                        let position = position(schema, key.as_str().unwrap_or(""));
+                        resolved.insert(key.clone(), self.resolve_child(value, file, position)?);
+                    }
+                    Ok(Value::Mapping(resolved))
+                }
+            },
+            Value::Sequence(list) => list
+                .iter()
+                .map(|value| self.resolve_value(value, file, false))
+                .collect::<Result<_, _>>()
+                .map(Value::Sequence),
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn resolve_child(
+        &mut self,
+        value: &Value,
+        file: &Path,
+        position: Position,
+    ) -> Result<Value, DaedalusError> {
+        match (position, value) {
+            (Position::Schema, value) => self.resolve_value(value, file, true),
+            (Position::Schemas, Value::Mapping(map)) => {
+                let mut resolved = Mapping::new();
+                for (key, value) in map {
+                    resolved.insert(key.clone(), self.resolve_value(value, file, true)?);
+                }
+                Ok(Value::Mapping(resolved))
+            }
+            (Position::Schemas, Value::Sequence(list)) => list
+                .iter()
+                .map(|value| self.resolve_value(value, file, true))
+                .collect::<Result<_, _>>()
+                .map(Value::Sequence),
+            (Position::Parameters, value) => {
+                let in_parameters = std::mem::replace(&mut self.in_parameters, true);
+                let resolved = self.resolve_value(value, file, false);
+                self.in_parameters = in_parameters;
+                resolved
+            }
+            (_, value) => self.resolve_value(value, file, false),
+        }
+    }
+
+    fn resolve_reference(
+        &mut self,
+        value: &Value,
+        reference: &str,
+        file: &Path,
+        schema: bool,
+    ) -> Result<Value, DaedalusError> {
+        let (target_name, pointer) = match reference.find('#') {
+            Some(index) => (&reference[..index], &reference[index + 1..]),
+            None => (reference, ""),
+        };
+        let target = if target_name.is_empty() {
+            file.to_path_buf()
+        } else {
+            canonical(
+                &file
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(target_name),
+            )
+        };
+        // The main file keeps its references, pointing back to it is just a local reference
+        if target == self.root {
+            return Ok(if file == self.root {
+                value.clone()
+            } else {
+                local_reference(pointer)
+            });
+        }
+
+        let key = format!("{}#{}", target.display(), pointer);
+        if let Some(name) = self.names.get(&key).filter(|_| !self.in_parameters) {
+            return Ok(self.schema_reference(name));
+        }
+        if let Some(start) = self.stack.iter().position(|resolving| *resolving == key) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(key);
+            return Err(DaedalusError::ReferenceCycle {
+                reference: reference.to_string(),
+                filename: file.display().to_string(),
+                cycle: cycle.join(" -> "),
+            });
+        }
+
+        let found = find(self.document(&target)?, pointer).cloned();
+        let found = found.ok_or_else(|| DaedalusError::UnresolvedReference {
+            reference: reference.to_string(),
+            filename: file.display().to_string(),
+            target: target.display().to_string(),
+            pointer: pointer.to_string(),
+        })?;
+
+        // A reference to another reference is followed until a schema with content is found,
+        // only a chain of references without any content is a cycle.
+        // Parameters can not point to other schemas, recursive parameter schemas are cycles too.
+        if schema && !self.in_parameters && found.get("$ref").is_none() {
+            let name = self.schema_name(&target, pointer);
+            self.names.insert(key, name.clone());
+            let stack = std::mem::take(&mut self.stack);
+            let resolved = self.resolve_value(&found, &target, true);
+            self.stack = stack;
+            self.schemas.insert(Value::String(name.clone()), resolved?);
+            return Ok(self.schema_reference(&name));
+        }
+        self.stack.push(key);
+        let resolved = self.resolve_value(&found, &target, schema);
+        self.stack.pop();
+        resolved
+    }
+
+    // The last part of the pointer or the name of the file, not used by other schemas
+    fn schema_name(&self, target: &Path, pointer: &str) -> String {
+        let base = pointer
+            .rsplit('/')
+            .next()
+            .filter(|token| !token.is_empty())
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .or_else(|| {
+                target
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| String::from("Schema"));
+        let root = &self.documents[&self.root];
+        unused_name(&base, |name| {
+            self.names.values().any(|taken| taken == name)
+                || find(root, &format!("{}/{}", self.schemas_pointer, name)).is_some()
+        })
+    }
+
+    fn schema_reference(&self, name: &str) -> Value {
+        local_reference(&format!("{}/{}", self.schemas_pointer, name))
+    }
+
+    fn insert_schemas(&mut self, document: &mut Value) {
+        let schemas = std::mem::take(&mut self.schemas);
+        insert_schemas(document, &self.schemas_pointer, schemas);
+    }
+
+    fn document(&mut self, path: &Path) -> Result<&Value, DaedalusError> {
+        if !self.documents.contains_key(path) {
+            let name = path.display().to_string();
+            let contents =
+                std::fs::read_to_string(path).map_err(|source| DaedalusError::NotFound {
+                    filename: name.clone(),
+                    source,
+                })?;
+            let document =
+                serde_yaml::from_str(&contents).map_err(|source| DaedalusError::Parsing {
+                    basename: name,
+                    source,
+                })?;
+            self.documents.insert(path.to_path_buf(), document);
+        }
+        Ok(&self.documents[path])
+    }
+}
+
+// Values of schemas are schemas or maps or lists of them, other values only have them under `schema`
+fn position(schema: bool, key: &str) -> Position {
+    match (schema, key) {
+        (true, "items") | (true, "additionalProperties") | (true, "not") | (false, "schema") => {
+            Position::Schema
+        }
+        (true, "properties")
+        | (true, "patternProperties")
+        | (true, "allOf")
+        | (true, "anyOf")
+        | (true, "oneOf")
+        | (false, "schemas")
+        | (false, "definitions") => Position::Schemas,
+        (false, "parameters") => Position::Parameters,
+        _ => Position::Other,
+    }
+}
+
+// The name with the characters not allowed in component names replaced, numbered when it is taken
+pub(crate) fn unused_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    let base: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "._-".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut name = base.clone();
+    let mut count = 1;
+    while taken(&name) {
+        count += 1;
+        name = format!("{}{}", base, count);
+    }
+    name
+}
+
+// Adds the schemas to the map at the pointer, creating the maps missing along the way
+pub(crate) fn insert_schemas(document: &mut Value, pointer: &str, schemas: Mapping) {
+    if schemas.is_empty() {
+        return;
+    }
+    let mut current = document;
+    for token in pointer.split('/').skip(1) {
+        let map = match current {
+            Value::Mapping(map) => map,
+            _ => return,
+        };
+        let token = Value::String(token.to_string());
+        if !matches!(map.get(&token), Some(Value::Mapping(_))) {
+            map.insert(token.clone(), Value::Mapping(Mapping::new()));
+        }
+        current = map.get_mut(&token).unwrap();
+    }
+    if let Value::Mapping(current) = current {
+        current.extend(schemas);
+    }
+}
+
+// Files are identified by their full path, the same file can be referenced with different relative paths
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+pub(crate) fn local_reference(pointer: &str) -> Value {
+    let mut map = Mapping::new();
+    map.insert(
+        Value::String("$ref".to_string()),
+        Value::String(format!("#{}", pointer)),
+    );
+    Value::Mapping(map)
+}
+
+// The value at the JSON pointer, ex. `/components/schemas/User`. The empty pointer is the whole document.
+pub(crate) fn find<'a>(document: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .try_fold(document, |value, token| match value {
+            Value::Mapping(map) => map.get(&Value::String(token)),
+            Value::Sequence(list) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| list.get(index)),
+            _ => None,
+        })
+}
@@ -1,9 +1,10 @@
-use crate::request_param::RequestParam;
-use http::header::{HeaderValue, ACCEPT, CONTENT_TYPE};
+use crate::request_param::{ParamLocation, RequestParam};
+use http::header::{HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE, COOKIE};
 use http::Method;
+use tracing::warn;
 
 /// This is a request that can be consumed by the users of this library
-pub type Request = http::Request<&'static str>;
+pub type Request = http::Request<String>;
 
 // This is the struct that we will store on each scenario.
 // Note that http::Request does not have a clone method
@@ -12,12 +13,20 @@ pub type Request = http::Request<&'static str>;
 // To allow them to do whatever they want, we provide a copy
 #[derive(Debug)]
 pub(crate) struct ScenarioRequest {
-    pub(crate) request: http::Request<&'static str>,
+    pub(crate) request: http::Request<String>,
+}
+
+impl Clone for ScenarioRequest {
+    fn clone(&self) -> Self {
+        ScenarioRequest {
+            request: self.to_request(),
+        }
+    }
 }
 
 impl ScenarioRequest {
-    pub(crate) fn clone(&self) -> Request {
-        let mut clone = http::Request::new("");
+    pub(crate) fn to_request(&self) -> Request {
+        let mut clone = http::Request::new(self.request.body().clone());
         *clone.method_mut() = self.request.method().clone();
         *clone.uri_mut() = self.request.uri().clone();
         *clone.version_mut() = self.request.version();
@@ -33,6 +42,10 @@ pub(crate) struct RequestBuilder {
     content_type: String,
     method: String,
     query_params: Vec<RequestParam>,
+    header_params: Vec<RequestParam>,
+    cookie_params: Vec<RequestParam>,
+    path_params: Vec<RequestParam>,
+    body: String,
 }
 
 impl RequestBuilder {
@@ -42,6 +55,10 @@ impl RequestBuilder {
             content_type: "json".to_string(),
             method: "get".to_string(),
             query_params: vec![],
+            header_params: vec![],
+            cookie_params: vec![],
+            path_params: vec![],
+            body: String::new(),
         }
     }
 
@@ -50,8 +67,20 @@ impl RequestBuilder {
         self
     }
 
-    pub(crate) fn query_params(&mut self, params: RequestParam) -> &mut Self {
-        self.query_params.push(params);
+    pub(crate) fn params(&mut self, params: Vec<RequestParam>) -> &mut Self {
+        for param in params {
+            self.param(param);
+        }
+        self
+    }
+
+    pub(crate) fn param(&mut self, param: RequestParam) -> &mut Self {
+        match param.location {
+            ParamLocation::Query => self.query_params.push(param),
+            ParamLocation::Header => self.header_params.push(param),
+            ParamLocation::Cookie => self.cookie_params.push(param),
+            ParamLocation::Path => self.path_params.push(param),
+        }
         self
     }
 
@@ -65,25 +94,64 @@ impl RequestBuilder {
         self
     }
 
-    pub(crate) fn build(self) -> ScenarioRequest {
+    pub(crate) fn body(&mut self, body: String) -> &mut Self {
+        self.body = body;
+        self
+    }
+
+    // None when the method, content type or URI can not be sent, the scenario is skipped
+    pub(crate) fn build(self) -> Option<ScenarioRequest> {
         let mut builder = http::request::Builder::new();
+        let method = match Method::from_bytes(self.method.as_bytes()) {
+            Ok(method) => method,
+            Err(_) => {
+                warn!("Can not send {} as an HTTP method", self.method);
+                return None;
+            }
+        };
+        let content_type = match HeaderValue::from_str(&self.content_type) {
+            Ok(content_type) => content_type,
+            Err(_) => {
+                warn!("Can not send {} as a content type", self.content_type);
+                return None;
+            }
+        };
 
         let headers = builder.headers_mut().unwrap();
-        headers.insert(ACCEPT, HeaderValue::from_str(&self.content_type).unwrap());
-        if self.is_method_with_data() {
-            headers.insert(
-                CONTENT_TYPE,
-                HeaderValue::from_str(&self.content_type).unwrap(),
-            );
+        headers.insert(ACCEPT, content_type.clone());
+        if Self::is_method_with_data(&method) {
+            headers.insert(CONTENT_TYPE, content_type);
+        }
+        for param in self.header_params.iter() {
+            if let Some(value) = &param.value {
+                match (
+                    HeaderName::from_bytes(param.name.as_bytes()),
+                    HeaderValue::from_str(value),
+                ) {
+                    (Ok(name), Ok(value)) => {
+                        headers.insert(name, value);
+                    }
+                    _ => warn!("Can not send {}: {} as an HTTP header", param.name, value),
+                }
+            }
+        }
+        if let Some(cookies) = self.cookies() {
+            match HeaderValue::from_str(&cookies) {
+                Ok(value) => {
+                    headers.insert(COOKIE, value);
+                }
+                Err(_) => warn!("Can not send {} as a Cookie header", cookies),
+            }
         }
 
-        let request = builder
-            .method(self.to_http_method())
-            .uri(&self.path_and_query())
-            .body("")
-            .unwrap();
-
-        ScenarioRequest { request }
+        let uri = self.path_and_query();
+        match builder.method(method).uri(&uri).body(self.body) {
+            Ok(request) => Some(ScenarioRequest { request }),
+            Err(_) => {
+                warn!("Can not send a request to {}", uri);
+                None
+            }
+        }
     }
 
     fn path_and_query(&self) -> String {
@@ -91,26 +159,79 @@ impl RequestBuilder {
         if !self.query_params.is_empty() {
             param_string = "?".to_string();
             for query_param in self.query_params.iter() {
-                if query_param.value.is_some() {
+                if let Some(value) = &query_param.value {
                     param_string.push_str(&format!(
                         "{}={}&",
-                        query_param.name,
-                        query_param.value.as_ref().unwrap()
+                        encode_query(&query_param.name),
+                        encode_query(value)
                     ));
                 }
             }
             let len = param_string.len();
             param_string.truncate(len - 1);
         }
-        format!("{}{}", self.path, param_string)
+        format!("{}{}", self.filled_path(), param_string)
+    }
+
+    // The path template with the variables replaced by the encoded path parameters
+    fn filled_path(&self) -> String {
+        let mut path = self.path.clone();
+        for param in self.path_params.iter() {
+            if let Some(value) = &param.value {
+                path = path.replace(&format!("{{{}}}", param.name), &encode_path(value));
+            }
+        }
+        path
+    }
+
+    // All the cookie parameters go together in one header: "a=b; c=d"
+    fn cookies(&self) -> Option<String> {
+        let cookies: Vec<String> = self
+            .cookie_params
+            .iter()
+            .filter_map(|param| {
+                param
+                    .value
+                    .as_ref()
+                    .map(|value| format!("{}={}", param.name, value))
+            })
+            .collect();
+        if cookies.is_empty() {
+            None
+        } else {
+            Some(cookies.join("; "))
+        }
     }
 
-    fn to_http_method(&self) -> Method {
-        Method::from_bytes(self.method.as_bytes()).unwrap()
+    fn is_method_with_data(method: &Method) -> bool {
+        method == Method::PATCH || method == Method::POST || method == Method::PUT
     }
+}
+
+// Percent-encodes the characters with a meaning in the query string or not allowed in an URI.
+// '%' is kept as is so already encoded values in the conversions file still work.
+// Brackets and pipes are valid and left readable: "filter[status]=active", "ids=1|2".
+fn encode_query(component: &str) -> String {
+    percent_encode(component, b"&+=")
+}
 
-    fn is_method_with_data(&self) -> bool {
-        let my_method = self.to_http_method();
-        my_method == Method::PATCH || my_method == Method::POST || my_method == Method::PUT
+// A path parameter is a single segment, it can not add segments or start the query
+fn encode_path(component: &str) -> String {
+    percent_encode(component, b"/?")
+}
+
+// Percent-encodes the characters not allowed in an URI and the reserved ones
+fn percent_encode(component: &str, reserved: &[u8]) -> String {
+    let mut encoded = String::new();
+    for byte in component.bytes() {
+        match byte {
+            b' ' | b'"' | b'#' | b'<' | b'>' | b'`' | b'{' | b'}' | b'\\' | b'^' => {
+                encoded.push_str(&format!("%{:02X}", byte))
+            }
+            byte if reserved.contains(&byte) => encoded.push_str(&format!("%{:02X}", byte)),
+            byte if byte.is_ascii_graphic() => encoded.push(byte as char),
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
     }
+    encoded
 }
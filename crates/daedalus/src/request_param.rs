@@ -1,10 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Where in the request the parameter is sent
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamLocation {
+    Query,
+    Header,
+    Cookie,
+    Path,
+}
+
+impl From<&openapiv3::Parameter> for ParamLocation {
+    fn from(param: &openapiv3::Parameter) -> Self {
+        match param {
+            openapiv3::Parameter::Header { .. } => ParamLocation::Header,
+            openapiv3::Parameter::Cookie { .. } => ParamLocation::Cookie,
+            openapiv3::Parameter::Path { .. } => ParamLocation::Path,
+            _ => ParamLocation::Query,
+        }
+    }
+}
+
+impl fmt::Display for ParamLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamLocation::Query => f.write_str("query"),
+            ParamLocation::Header => f.write_str("header"),
+            ParamLocation::Cookie => f.write_str("cookie"),
+            ParamLocation::Path => f.write_str("path"),
+        }
+    }
+}
+
 // This is the Spec Request param information and helper methods.
 // We want to be able to render the name of the parameter that we do send
 // On value: None the value is not serialized.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Serialize, Deserialize)]
 pub struct RequestParam {
     pub name: String,
     pub value: Option<String>,
+    pub location: ParamLocation,
 }
 
 impl RequestParam {
@@ -12,14 +48,20 @@ impl RequestParam {
         RequestParam {
             name: name.to_string(),
             value: Some(value.to_string()),
+            location: ParamLocation::Query,
         }
     }
     pub(crate) fn new2(name: &str, value: Option<String>) -> Self {
         RequestParam {
             name: name.to_string(),
             value,
+            location: ParamLocation::Query,
         }
     }
+    pub(crate) fn located(mut self, location: ParamLocation) -> Self {
+        self.location = location;
+        self
+    }
 }
 // use crate::mutation::param_mutation::ParamMutation;
 
@@ -1,75 +1,175 @@
+use crate::mutation::instructions::{Mutagen, RequestPart};
 use crate::mutation::Mutation;
 use crate::operation;
 use crate::request::{Request, ScenarioRequest};
 use http::StatusCode;
 use openapi_utils::OperationExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fmt;
 
-/// An auto-generated scenario
-#[derive(Debug)]
-pub struct Scenario<'a> {
+/// An auto-generated scenario.
+/// Scenarios can be serialized to review or edit them and run them later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "SerializedScenario", try_from = "SerializedScenario")]
+pub struct Scenario {
+    id: String,
     /// The instructions we used to build this scenario
     pub instructions: Vec<Mutation>,
     request: ScenarioRequest,
-    expectation: ScenarioExpectation<'a>,
+    expectation: ScenarioExpectation,
+    server_origin: Option<String>,
 }
 
 /// The expectation to pass for a given scenario
-#[derive(Debug)]
-pub struct ScenarioExpectation<'a> {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioExpectation {
     /// Expected status code
+    #[serde(with = "crate::status_code")]
     pub status_code: StatusCode,
     /// Expected body
-    pub body: Option<&'a openapiv3::Response>,
+    pub body: Option<openapiv3::Response>,
     /// Expected content type
     pub content_type: String,
 }
 
-impl fmt::Display for Scenario<'_> {
+// The request is written field by field so the file is easy to read and edit
+#[derive(Serialize, Deserialize)]
+struct SerializedScenario {
+    id: String,
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    server_origin: Option<String>,
+    mutations: Vec<Mutation>,
+    expectation: ScenarioExpectation,
+}
+
+impl From<Scenario> for SerializedScenario {
+    fn from(scenario: Scenario) -> Self {
+        let request = scenario.request.request;
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        SerializedScenario {
+            id: scenario.id,
+            method: request.method().to_string(),
+            url: request.uri().to_string(),
+            headers,
+            body: request.body().clone(),
+            server_origin: scenario.server_origin,
+            mutations: scenario.instructions,
+            expectation: scenario.expectation,
+        }
+    }
+}
+
+impl TryFrom<SerializedScenario> for Scenario {
+    type Error = String;
+    fn try_from(serialized: SerializedScenario) -> Result<Self, Self::Error> {
+        let mut builder = http::Request::builder()
+            .method(serialized.method.as_str())
+            .uri(serialized.url.as_str());
+        for (name, value) in &serialized.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let id = &serialized.id;
+        let request = builder
+            .body(serialized.body)
+            .map_err(|error| format!("Scenario `{}` has a wrong request: {}", id, error))?;
+        Ok(Scenario {
+            id: serialized.id,
+            instructions: serialized.mutations,
+            request: ScenarioRequest { request },
+            expectation: serialized.expectation,
+            server_origin: serialized.server_origin,
+        })
+    }
+}
+
+impl fmt::Display for Scenario {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let request = &self.request.request;
         write!(f, "{} {}", request.method(), request.uri())
     }
 }
 
-impl<'a> Scenario<'a> {
+impl Scenario {
     pub(crate) fn new(
-        endpoint: &'a operation::Endpoint,
+        endpoint: &operation::Endpoint,
+        id: String,
         instructions: Vec<Mutation>,
         request: ScenarioRequest,
     ) -> Self {
-        let status_code = Self::expected_status_code(&instructions);
+        let status_code = Self::expected_status_code(&instructions, endpoint.success_status());
 
         let expectation = ScenarioExpectation {
             status_code,
-            body: endpoint.method.response(status_code.as_u16()),
+            body: endpoint.method.response(status_code.as_u16()).cloned(),
             content_type: String::from("application/json"),
         };
 
         Scenario {
+            id,
             instructions,
             request,
             expectation,
+            server_origin: endpoint.server_origin.clone(),
         }
     }
 
+    /// Identifies the scenario between runs. It depends on the operation and on what is changed
+    /// in the request, not on the values used, so it is the same even if the values are random.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     /// Returns a new runnable request from the scenario
     pub fn request(&self) -> Request {
-        self.request.clone()
+        self.request.to_request()
+    }
+
+    /// The scheme and host of the server of the operation, ex. `https://api.example.com`.
+    /// Paths and operations with their own servers may be on another host than the openapi file.
+    pub fn server_origin(&self) -> Option<&str> {
+        self.server_origin.as_deref()
     }
 
     /// The expectations for this scenario
-    pub fn expectation(&self) -> &ScenarioExpectation<'_> {
+    pub fn expectation(&self) -> &ScenarioExpectation {
         &self.expectation
     }
 
-    fn expected_status_code(instructions: &[Mutation]) -> StatusCode {
+    /// True when the scenario sends no credentials or invalid ones on purpose, to check the operation
+    /// rejects them or is public. The request should be sent without authenticating it in any other way.
+    pub fn without_valid_credentials(&self) -> bool {
+        self.instructions.iter().any(|instruction| {
+            instruction.mutagen.request_part == RequestPart::Credentials
+                && instruction.mutagen.mutagen != Mutagen::CredentialsProper
+        })
+    }
+
+    // Passing instructions are marked with a generic success code, when all of them pass
+    // the scenario expects the success code documented for the endpoint.
+    fn expected_status_code(instructions: &[Mutation], success: StatusCode) -> StatusCode {
         match instructions
             .iter()
-            .find(|i| i.mutagen.expected != StatusCode::OK)
+            .find(|i| !i.mutagen.expected.is_success())
         {
             Some(instruction) => instruction.mutagen.expected,
-            None => StatusCode::OK,
+            None => success,
         }
     }
 }
@@ -0,0 +1,156 @@
+use crate::error::DaedalusError;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Which of the servers of the openapi file is used
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerSelection {
+    /// The position of the server in the list, starting at 0
+    Index(usize),
+    /// The first server whose description contains this text, ignoring case
+    Description(String),
+}
+
+/// Numbers select servers by their position, any other text by their description.
+impl FromStr for ServerSelection {
+    type Err = std::convert::Infallible;
+
+    fn from_str(selection: &str) -> Result<Self, Self::Err> {
+        Ok(match selection.parse() {
+            Ok(index) => ServerSelection::Index(index),
+            Err(_) => ServerSelection::Description(selection.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for ServerSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerSelection::Index(index) => write!(f, "{}", index),
+            ServerSelection::Description(description) => f.write_str(description),
+        }
+    }
+}
+
+// Resolves the URLs of the servers of the spec, paths and operations
+pub(crate) struct Servers {
+    selection: ServerSelection,
+    variables: BTreeMap<String, String>,
+}
+
+impl Servers {
+    pub(crate) fn new(selection: ServerSelection, variables: BTreeMap<String, String>) -> Self {
+        Servers {
+            selection,
+            variables,
+        }
+    }
+
+    // The URL of the selected server of the spec. Not finding it is an error, the user asked for it.
+    pub(crate) fn spec_url(
+        &self,
+        servers: &[openapiv3::Server],
+    ) -> Result<Option<String>, DaedalusError> {
+        if servers.is_empty() {
+            return Ok(None);
+        }
+        match self.find(servers) {
+            Some(server) => self.url(server).map(Some),
+            None => Err(DaedalusError::ServerNotFound {
+                selection: self.selection.to_string(),
+            }),
+        }
+    }
+
+    // Paths and operations overriding the servers use the selected one if they have it, their first one if not
+    pub(crate) fn override_url(
+        &self,
+        servers: &[openapiv3::Server],
+    ) -> Result<Option<String>, DaedalusError> {
+        match self.find(servers).or_else(|| servers.first()) {
+            Some(server) => self.url(server).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn find<'a>(&self, servers: &'a [openapiv3::Server]) -> Option<&'a openapiv3::Server> {
+        match &self.selection {
+            ServerSelection::Index(index) => servers.get(*index),
+            ServerSelection::Description(text) => {
+                let text = text.to_lowercase();
+                servers.iter().find(|server| {
+                    server
+                        .description
+                        .iter()
+                        .any(|description| description.to_lowercase().contains(&text))
+                })
+            }
+        }
+    }
+
+    // The URL with its `{variables}` replaced by the values given by the user or their defaults
+    fn url(&self, server: &openapiv3::Server) -> Result<String, DaedalusError> {
+        let mut url = String::new();
+        let mut rest = server.url.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+            url.push_str(&rest[..start]);
+            url.push_str(&self.value(server, &rest[start + 1..end])?);
+            rest = &rest[end + 1..];
+        }
+        url.push_str(rest);
+        Ok(url)
+    }
+
+    fn value(&self, server: &openapiv3::Server, name: &str) -> Result<String, DaedalusError> {
+        let variable = server
+            .variables
+            .as_ref()
+            .and_then(|variables| variables.get(name));
+        match (self.variables.get(name), variable) {
+            (Some(value), Some(variable))
+                if !variable.enumeration.is_empty() && !variable.enumeration.contains(value) =>
+            {
+                Err(DaedalusError::ServerVariable {
+                    name: name.to_string(),
+                    value: value.clone(),
+                    allowed: variable.enumeration.join(", "),
+                })
+            }
+            (Some(value), _) => Ok(value.clone()),
+            (None, Some(variable)) => Ok(variable.default.clone()),
+            (None, None) => Err(DaedalusError::MissingServerVariable {
+                name: name.to_string(),
+                url: server.url.clone(),
+            }),
+        }
+    }
+}
+
+// Splits a server URL into its origin, ex. `https://example.com`, and its base path, ex. `/v1`.
+// Relative URLs have no origin.
+pub(crate) fn split(url: &str) -> (Option<&str>, &str) {
+    let url = url.trim_end_matches('/');
+    match url.find("://") {
+        Some(scheme_end) => {
+            let host_start = scheme_end + 3;
+            match url[host_start..].find('/') {
+                Some(path_start) => (
+                    Some(&url[..host_start + path_start]),
+                    &url[host_start + path_start..],
+                ),
+                None => (Some(url), ""),
+            }
+        }
+        None => (None, url),
+    }
+}
+
+// The origin of the URL, None for relative URLs and when there is no URL
+pub(crate) fn origin(url: Option<&str>) -> Option<String> {
+    url.and_then(|url| split(url).0).map(String::from)
+}
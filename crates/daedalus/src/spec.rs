@@ -1,10 +1,50 @@
 use crate::error::DaedalusError;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 pub(crate) fn read<T: DeserializeOwned>(filename: &str) -> Result<T, DaedalusError> {
     read_yaml(filename.to_owned(), read_file(filename)?)
 }
 
+// The openapi file with the references to other files replaced by their contents.
+// Swagger 2.0 and OpenAPI 3.1 files are converted to OpenAPI 3.0.
+pub(crate) fn read_openapi(filename: &str) -> Result<openapiv3::OpenAPI, DaedalusError> {
+    let mut document = crate::reference::Resolver::new(filename).resolve()?;
+    if crate::swagger2::is_swagger_2(&document) {
+        document = crate::swagger2::convert(document);
+    }
+    if crate::openapi31::is_openapi_31(&document) {
+        document = crate::openapi31::convert(document, filename)?;
+    }
+    // Back to text, values like `version: 1.0` are only read as strings from the text
+    let parsing = |source| DaedalusError::Parsing {
+        basename: filename.to_owned(),
+        source,
+    };
+    let contents = serde_yaml::to_string(&document).map_err(parsing)?;
+    read_yaml(filename.to_owned(), contents)
+}
+
+// JSON for .json files and YAML for everything else
+pub(crate) fn write<T: Serialize>(filename: &str, data: &T) -> Result<(), DaedalusError> {
+    let contents = if filename.ends_with(".json") {
+        serde_json::to_string_pretty(data).map_err(|source| DaedalusError::JsonSerializing {
+            filename: filename.to_owned(),
+            source,
+        })?
+    } else {
+        serde_yaml::to_string(data).map_err(|source| DaedalusError::YamlSerializing {
+            filename: filename.to_owned(),
+            source,
+        })?
+    };
+    let path = shellexpand::tilde(filename).into_owned();
+    std::fs::write(&path, contents).map_err(|source| DaedalusError::Writing {
+        filename: filename.to_owned(),
+        source,
+    })
+}
+
 fn read_file(basename: &str) -> Result<String, DaedalusError> {
     let filename = shellexpand::tilde(basename).into_owned();
     std::fs::read_to_string(&filename)
@@ -0,0 +1,19 @@
+use http::StatusCode;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+// http::StatusCode does not implement serde, we write the number
+pub(crate) fn serialize<S: Serializer>(
+    status: &StatusCode,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u16(status.as_u16())
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<StatusCode, D::Error> {
+    let code = u16::deserialize(deserializer)?;
+    StatusCode::from_u16(code)
+        .map_err(|_| D::Error::custom(format!("`{}` is not a valid status code", code)))
+}
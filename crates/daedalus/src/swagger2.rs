@@ -0,0 +1,500 @@
+use serde_yaml::{Mapping, Value};
+
+// Keywords of Swagger parameters and headers which are part of the schema in OpenAPI 3
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+pub(crate) fn is_swagger_2(document: &Value) -> bool {
+    match document.get("swagger") {
+        Some(Value::String(version)) => version.starts_with("2."),
+        _ => false,
+    }
+}
+
+// Rewrites a Swagger 2.0 document as the OpenAPI 3.0 document with the same meaning
+pub(crate) fn convert(document: Value) -> Value {
+    let swagger = match document {
+        Value::Mapping(swagger) => swagger,
+        other => return other,
+    };
+    let converter = Converter {
+        consumes: media_types(swagger.get(&key("consumes")))
+            .unwrap_or_else(|| vec![DEFAULT_MEDIA_TYPE.to_string()]),
+        produces: media_types(swagger.get(&key("produces")))
+            .unwrap_or_else(|| vec![DEFAULT_MEDIA_TYPE.to_string()]),
+        parameters: mapping(swagger.get(&key("parameters"))),
+    };
+
+    let mut openapi = Mapping::new();
+    openapi.insert(key("openapi"), key("3.0.3"));
+    for name in &["info", "security", "tags", "externalDocs"] {
+        if let Some(value) = swagger.get(&key(name)) {
+            openapi.insert(key(name), value.clone());
+        }
+    }
+    copy_extensions(&swagger, &mut openapi);
+    if let Some(url) = server_url(&swagger) {
+        let mut server = Mapping::new();
+        server.insert(key("url"), Value::String(url));
+        openapi.insert(
+            key("servers"),
+            Value::Sequence(vec![Value::Mapping(server)]),
+        );
+    }
+
+    let mut paths = Mapping::new();
+    for (path_name, item) in mapping(swagger.get(&key("paths"))) {
+        paths.insert(
+            path_name,
+            Value::Mapping(converter.path_item(&mapping(Some(&item)))),
+        );
+    }
+    openapi.insert(key("paths"), Value::Mapping(paths));
+    openapi.insert(
+        key("components"),
+        Value::Mapping(converter.components(&swagger)),
+    );
+
+    let mut openapi = Value::Mapping(openapi);
+    convert_value(&mut openapi);
+    openapi
+}
+
+struct Converter {
+    consumes: Vec<String>,
+    produces: Vec<String>,
+    // The parameters of the root, they can be used by reference
+    parameters: Mapping,
+}
+
+impl Converter {
+    fn components(&self, swagger: &Mapping) -> Mapping {
+        let mut components = Mapping::new();
+        if let Some(definitions) = swagger.get(&key("definitions")) {
+            components.insert(key("schemas"), definitions.clone());
+        }
+
+        // Body and form parameters become request bodies, they are copied where they are used
+        let mut parameters = Mapping::new();
+        for (name, parameter) in &self.parameters {
+            let parameter = mapping(Some(parameter));
+            if !is_body(&parameter) {
+                parameters.insert(name.clone(), Value::Mapping(parameter_data(&parameter)));
+            }
+        }
+        if !parameters.is_empty() {
+            components.insert(key("parameters"), Value::Mapping(parameters));
+        }
+
+        let mut responses = Mapping::new();
+        for (name, response) in mapping(swagger.get(&key("responses"))) {
+            responses.insert(name, self.response(&response, &self.produces));
+        }
+        if !responses.is_empty() {
+            components.insert(key("responses"), Value::Mapping(responses));
+        }
+
+        let mut schemes = Mapping::new();
+        for (name, scheme) in mapping(swagger.get(&key("securityDefinitions"))) {
+            schemes.insert(
+                name,
+                Value::Mapping(security_scheme(&mapping(Some(&scheme)))),
+            );
+        }
+        if !schemes.is_empty() {
+            components.insert(key("securitySchemes"), Value::Mapping(schemes));
+        }
+        components
+    }
+
+    fn path_item(&self, item: &Mapping) -> Mapping {
+        let (path_bodies, path_parameters) = self.split_parameters(item.get(&key("parameters")));
+        let mut converted = Mapping::new();
+        for (name, value) in item {
+            match name.as_str() {
+                Some("parameters") => {}
+                Some(method) if METHODS.contains(&method) => {
+                    let operation = self.operation(&mapping(Some(value)), &path_bodies);
+                    converted.insert(name.clone(), Value::Mapping(operation));
+                }
+                _ => {
+                    converted.insert(name.clone(), value.clone());
+                }
+            }
+        }
+        if !path_parameters.is_empty() {
+            converted.insert(key("parameters"), Value::Sequence(path_parameters));
+        }
+        converted
+    }
+
+    fn operation(&self, operation: &Mapping, path_bodies: &[Mapping]) -> Mapping {
+        let consumes =
+            media_types(operation.get(&key("consumes"))).unwrap_or_else(|| self.consumes.clone());
+        let produces =
+            media_types(operation.get(&key("produces"))).unwrap_or_else(|| self.produces.clone());
+        let (mut bodies, parameters) = self.split_parameters(operation.get(&key("parameters")));
+        bodies.extend(path_bodies.iter().cloned());
+
+        let mut converted = Mapping::new();
+        for (name, value) in operation {
+            match name.as_str() {
+                Some("parameters") | Some("responses") | Some("consumes") | Some("produces")
+                | Some("schemes") => {}
+                _ => {
+                    converted.insert(name.clone(), value.clone());
+                }
+            }
+        }
+        if !parameters.is_empty() {
+            converted.insert(key("parameters"), Value::Sequence(parameters));
+        }
+        if let Some(body) = request_body(&bodies, &consumes) {
+            converted.insert(key("requestBody"), Value::Mapping(body));
+        }
+        let mut responses = Mapping::new();
+        for (code, response) in mapping(operation.get(&key("responses"))) {
+            responses.insert(code, self.response(&response, &produces));
+        }
+        converted.insert(key("responses"), Value::Mapping(responses));
+        converted
+    }
+
+    // The body and form parameters, with their references resolved, and the converted rest of parameters
+    fn split_parameters(&self, parameters: Option<&Value>) -> (Vec<Mapping>, Vec<Value>) {
+        let mut bodies = Vec::new();
+        let mut converted = Vec::new();
+        for parameter in sequence(parameters) {
+            let parameter = mapping(Some(&parameter));
+            let resolved = match reference(&parameter) {
+                Some(reference) => match reference.strip_prefix("#/parameters/") {
+                    Some(name) => mapping(self.parameters.get(&key(name))),
+                    None => parameter.clone(),
+                },
+                None => parameter.clone(),
+            };
+            if is_body(&resolved) {
+                bodies.push(resolved);
+            } else if reference(&parameter).is_some() {
+                converted.push(Value::Mapping(parameter));
+            } else {
+                converted.push(Value::Mapping(parameter_data(&parameter)));
+            }
+        }
+        (bodies, converted)
+    }
+
+    fn response(&self, response: &Value, produces: &[String]) -> Value {
+        let response = mapping(Some(response));
+        if reference(&response).is_some() {
+            return Value::Mapping(response);
+        }
+        let mut converted = Mapping::new();
+        converted.insert(
+            key("description"),
+            response
+                .get(&key("description"))
+                .cloned()
+                .unwrap_or_else(|| key("")),
+        );
+        copy_extensions(&response, &mut converted);
+        if let Some(schema) = response.get(&key("schema")) {
+            let examples = mapping(response.get(&key("examples")));
+            let mut content = Mapping::new();
+            for media_type in produces {
+                let mut media = Mapping::new();
+                media.insert(key("schema"), schema.clone());
+                if let Some(example) = examples.get(&key(media_type)) {
+                    media.insert(key("example"), example.clone());
+                }
+                content.insert(key(media_type), Value::Mapping(media));
+            }
+            converted.insert(key("content"), Value::Mapping(content));
+        }
+        let mut headers = Mapping::new();
+        for (name, header) in mapping(response.get(&key("headers"))) {
+            let header = mapping(Some(&header));
+            let mut converted_header = Mapping::new();
+            if let Some(description) = header.get(&key("description")) {
+                converted_header.insert(key("description"), description.clone());
+            }
+            converted_header.insert(key("schema"), Value::Mapping(schema(&header)));
+            headers.insert(name, Value::Mapping(converted_header));
+        }
+        if !headers.is_empty() {
+            converted.insert(key("headers"), Value::Mapping(headers));
+        }
+        Value::Mapping(converted)
+    }
+}
+
+// A query, path, header or cookie parameter, its type goes to its schema
+fn parameter_data(parameter: &Mapping) -> Mapping {
+    let mut converted = Mapping::new();
+    for name in &["name", "in", "description", "required", "allowEmptyValue"] {
+        if let Some(value) = parameter.get(&key(name)) {
+            converted.insert(key(name), value.clone());
+        }
+    }
+    copy_extensions(parameter, &mut converted);
+    let location = parameter.get(&key("in")).and_then(Value::as_str);
+    if parameter.get(&key("type")).and_then(Value::as_str) == Some("array") {
+        // Arrays are comma separated by default in Swagger
+        let format = parameter
+            .get(&key("collectionFormat"))
+            .and_then(Value::as_str)
+            .unwrap_or("csv");
+        let (style, explode) = match (format, location) {
+            ("multi", _) => ("form", true),
+            ("ssv", _) => ("spaceDelimited", false),
+            ("pipes", _) => ("pipeDelimited", false),
+            (_, Some("query")) => ("form", false),
+            _ => ("simple", false),
+        };
+        converted.insert(key("style"), key(style));
+        converted.insert(key("explode"), Value::Bool(explode));
+    }
+    converted.insert(key("schema"), Value::Mapping(schema(parameter)));
+    converted
+}
+
+// The request body out of the body parameter or out of the form parameters
+fn request_body(bodies: &[Mapping], consumes: &[String]) -> Option<Mapping> {
+    let mut body = Mapping::new();
+    let (schema, media_types) = match bodies
+        .iter()
+        .find(|parameter| location(parameter) == Some("body"))
+    {
+        Some(parameter) => {
+            if let Some(description) = parameter.get(&key("description")) {
+                body.insert(key("description"), description.clone());
+            }
+            let required = parameter.get(&key("required")).cloned();
+            body.insert(key("required"), required.unwrap_or(Value::Bool(false)));
+            (
+                parameter.get(&key("schema")).cloned().unwrap_or_default(),
+                consumes.to_vec(),
+            )
+        }
+        None if !bodies.is_empty() => {
+            let mut properties = Mapping::new();
+            let mut required = Vec::new();
+            for parameter in bodies {
+                let name = parameter.get(&key("name")).cloned().unwrap_or_default();
+                if parameter.get(&key("required")) == Some(&Value::Bool(true)) {
+                    required.push(name.clone());
+                }
+                properties.insert(name, Value::Mapping(schema(parameter)));
+            }
+            let mut schema = Mapping::new();
+            schema.insert(key("type"), key("object"));
+            schema.insert(key("properties"), Value::Mapping(properties));
+            if !required.is_empty() {
+                schema.insert(key("required"), Value::Sequence(required));
+                body.insert(key("required"), Value::Bool(true));
+            }
+            let media_type = if consumes.iter().any(|media| media == "multipart/form-data") {
+                "multipart/form-data"
+            } else {
+                "application/x-www-form-urlencoded"
+            };
+            (Value::Mapping(schema), vec![media_type.to_string()])
+        }
+        None => return None,
+    };
+    let mut content = Mapping::new();
+    for media_type in media_types {
+        let mut media = Mapping::new();
+        media.insert(key("schema"), schema.clone());
+        content.insert(key(&media_type), Value::Mapping(media));
+    }
+    body.insert(key("content"), Value::Mapping(content));
+    Some(body)
+}
+
+// The schema keywords of a parameter, header or array items
+fn schema(parameter: &Mapping) -> Mapping {
+    let mut converted = Mapping::new();
+    for name in SCHEMA_KEYWORDS {
+        if let Some(value) = parameter.get(&key(name)) {
+            let value = match (*name, value) {
+                ("items", Value::Mapping(items)) => Value::Mapping(schema(items)),
+                _ => value.clone(),
+            };
+            converted.insert(key(name), value);
+        }
+    }
+    converted
+}
+
+fn security_scheme(scheme: &Mapping) -> Mapping {
+    let get = |name: &str| scheme.get(&key(name)).cloned().unwrap_or_default();
+    let mut converted = Mapping::new();
+    match scheme.get(&key("type")).and_then(Value::as_str) {
+        Some("basic") => {
+            converted.insert(key("type"), key("http"));
+            converted.insert(key("scheme"), key("basic"));
+        }
+        Some("oauth2") => {
+            let (flow, urls): (&str, &[&str]) =
+                match scheme.get(&key("flow")).and_then(Value::as_str) {
+                    Some("implicit") => ("implicit", &["authorizationUrl"]),
+                    Some("password") => ("password", &["tokenUrl"]),
+                    Some("application") => ("clientCredentials", &["tokenUrl"]),
+                    _ => ("authorizationCode", &["authorizationUrl", "tokenUrl"]),
+                };
+            let mut flow_data = Mapping::new();
+            for url in urls {
+                flow_data.insert(key(url), get(url));
+            }
+            let scopes = scheme
+                .get(&key("scopes"))
+                .cloned()
+                .unwrap_or_else(|| Value::Mapping(Mapping::new()));
+            flow_data.insert(key("scopes"), scopes);
+            let mut flows = Mapping::new();
+            flows.insert(key(flow), Value::Mapping(flow_data));
+            converted.insert(key("type"), key("oauth2"));
+            converted.insert(key("flows"), Value::Mapping(flows));
+        }
+        _ => {
+            converted.insert(key("type"), key("apiKey"));
+            converted.insert(key("name"), get("name"));
+            converted.insert(key("in"), get("in"));
+        }
+    }
+    if let Some(description) = scheme.get(&key("description")) {
+        converted.insert(key("description"), description.clone());
+    }
+    converted
+}
+
+// The URL out of the scheme, host and base path. Relative when there is no host.
+fn server_url(swagger: &Mapping) -> Option<String> {
+    let base_path = swagger
+        .get(&key("basePath"))
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    match swagger.get(&key("host")).and_then(Value::as_str) {
+        Some(host) => {
+            let scheme = sequence(swagger.get(&key("schemes")))
+                .first()
+                .and_then(Value::as_str)
+                .unwrap_or("http")
+                .to_string();
+            Some(format!("{}://{}{}", scheme, host, base_path))
+        }
+        None if !base_path.is_empty() => Some(base_path.to_string()),
+        None => None,
+    }
+}
+
+// References to the root of the document and schema keywords which changed
+fn convert_value(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            for (_, value) in map.iter_mut() {
+                convert_value(value);
+            }
+            if let Some(Value::String(reference)) = map.get_mut(&key("$ref")) {
+                for (old, new) in &[
+                    ("#/definitions/", "#/components/schemas/"),
+                    ("#/parameters/", "#/components/parameters/"),
+                    ("#/responses/", "#/components/responses/"),
+                ] {
+                    if let Some(name) = reference.strip_prefix(old) {
+                        *reference = format!("{}{}", new, name);
+                    }
+                }
+            }
+            if map.get(&key("type")).and_then(Value::as_str) == Some("file") {
+                map.insert(key("type"), key("string"));
+                map.insert(key("format"), key("binary"));
+            }
+            if let Some(nullable) = map.remove(&key("x-nullable")) {
+                map.insert(key("nullable"), nullable);
+            }
+            if let Some(Value::String(property)) = map.get(&key("discriminator")).cloned() {
+                let mut discriminator = Mapping::new();
+                discriminator.insert(key("propertyName"), Value::String(property));
+                map.insert(key("discriminator"), Value::Mapping(discriminator));
+            }
+        }
+        Value::Sequence(list) => {
+            for value in list {
+                convert_value(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_body(parameter: &Mapping) -> bool {
+    matches!(location(parameter), Some("body") | Some("formData"))
+}
+
+fn location(parameter: &Mapping) -> Option<&str> {
+    parameter.get(&key("in")).and_then(Value::as_str)
+}
+
+fn reference(value: &Mapping) -> Option<&str> {
+    value.get(&key("$ref")).and_then(Value::as_str)
+}
+
+fn media_types(value: Option<&Value>) -> Option<Vec<String>> {
+    let types: Vec<String> = sequence(value)
+        .iter()
+        .filter_map(|media_type| media_type.as_str().map(String::from))
+        .collect();
+    if types.is_empty() {
+        None
+    } else {
+        Some(types)
+    }
+}
+
+fn copy_extensions(from: &Mapping, to: &mut Mapping) {
+    for (name, value) in from {
+        if matches!(name.as_str(), Some(name) if name.starts_with("x-")) {
+            to.insert(name.clone(), value.clone());
+        }
+    }
+}
+
+fn mapping(value: Option<&Value>) -> Mapping {
+    match value {
+        Some(Value::Mapping(map)) => map.clone(),
+        _ => Mapping::new(),
+    }
+}
+
+fn sequence(value: Option<&Value>) -> Vec<Value> {
+    match value {
+        Some(Value::Sequence(list)) => list.clone(),
+        _ => Vec::new(),
+    }
+}
+
+fn key(name: &str) -> Value {
+    Value::String(name.to_string())
+}
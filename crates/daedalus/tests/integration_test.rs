@@ -52,12 +52,16 @@ fn without_conversions_default_scenarios() {
     let generator = Generator::new(&config).unwrap();
     let scenarios: Vec<Scenario>  = generator.scenarios().collect();
 
-    assert_eq!(scenarios.len(), 5);
+    assert_eq!(scenarios.len(), 7);
     assert_eq!(scenarios[0].expectation().status_code, 200);
     assert_eq!(scenarios[1].expectation().status_code, 406);
     assert_eq!(scenarios[2].expectation().status_code, 200);
     assert_eq!(scenarios[3].expectation().status_code, 200);
-    assert_eq!(scenarios[4].expectation().status_code, 404);
+    // The default of the path parameter is used as its proper value
+    assert_eq!(scenarios[4].expectation().status_code, 200);
+    assert_eq!(scenarios[4].request().uri(), "/currencies/desc");
+    assert_eq!(scenarios[5].expectation().status_code, 406);
+    assert_eq!(scenarios[6].expectation().status_code, 404);
 }
 
 #[test]
@@ -71,10 +75,11 @@ fn without_conversions_without_errors_default_scenarios() {
     let generator = Generator::new(&config).unwrap();
     let scenarios: Vec<Scenario>  = generator.scenarios().collect();
 
-    assert_eq!(scenarios.len(), 3);
+    assert_eq!(scenarios.len(), 4);
     assert_eq!(scenarios[0].expectation().status_code, 200);
     assert_eq!(scenarios[1].expectation().status_code, 200);
     assert_eq!(scenarios[2].expectation().status_code, 200);
+    assert_eq!(scenarios[3].expectation().status_code, 200);
 }
 
 #[test]
@@ -95,6 +100,1077 @@ fn passing_existing_files() {
     assert_eq!(scenarios[2].expectation().status_code, 200);
     assert_eq!(scenarios[3].expectation().status_code, 200);
     assert_eq!(scenarios[4].expectation().status_code, 200);
-    assert_eq!(scenarios[5].expectation().status_code, 404);
-    assert_eq!(scenarios[6].expectation().status_code, 406);
+    assert_eq!(scenarios[5].expectation().status_code, 406);
+    assert_eq!(scenarios[6].expectation().status_code, 404);
+    assert_eq!(scenarios[6].request().uri(), "/currencies/NotValidValueForFormat");
+}
+
+#[test]
+fn path_params_with_configured_error_status() {
+    let mut config = GeneratorConfig::new(
+        support_file("test_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        true,
+        "/currencies/".to_string(),
+    );
+    config.path_error_status(http::StatusCode::UNPROCESSABLE_ENTITY);
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    assert_eq!(scenarios.len(), 3);
+    assert_eq!(scenarios[0].expectation().status_code, 200);
+    assert_eq!(scenarios[2].expectation().status_code, 422);
+    assert_eq!(scenarios[2].request().uri(), "/currencies/NotValidValueForFormat");
+}
+
+#[test]
+fn path_params_are_percent_encoded() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/folders".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    assert_eq!(scenarios[0].expectation().status_code, 200);
+    assert_eq!(scenarios[0].request().uri(), "/folders/My%20files%2F2020");
+}
+
+#[test]
+fn write_operations_expect_their_documented_success_code() {
+    let config = GeneratorConfig::new(
+        support_file("test_write_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        false,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    assert_eq!(scenarios.len(), 3);
+    assert_eq!(scenarios[0].request().method(), "POST");
+    assert_eq!(scenarios[0].expectation().status_code, 201);
+    assert_eq!(scenarios[1].request().method(), "PUT");
+    assert_eq!(scenarios[1].expectation().status_code, 200);
+    assert_eq!(scenarios[2].request().method(), "DELETE");
+    assert_eq!(scenarios[2].expectation().status_code, 204);
+}
+
+#[test]
+fn write_operations_send_a_valid_body() {
+    let config = GeneratorConfig::new(
+        support_file("test_write_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        false,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    assert_eq!(
+        scenarios[0].request().body(),
+        r#"{"code":"Dae","decimals":1,"name":"Dollar","symbol":"$"}"#
+    );
+    assert_eq!(scenarios[2].request().body(), "");
+}
+
+#[test]
+fn body_mutations_expect_unprocessable_entity() {
+    let config = GeneratorConfig::new(
+        support_file("test_write_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        true,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+
+    let body_scenarios: Vec<&Scenario> = scenarios
+        .iter()
+        .filter(|scenario| {
+            scenario.instructions.iter().any(|mutation| {
+                mutation.mutagen.request_part.to_string() == "The request body"
+                    && !mutation.mutagen.expected.is_success()
+            })
+        })
+        .collect();
+    assert_eq!(body_scenarios.len(), 11);
+    assert!(body_scenarios
+        .iter()
+        .all(|scenario| scenario.expectation().status_code == 422));
+
+    let missing_properties: Vec<String> = body_scenarios
+        .iter()
+        .filter(|scenario| {
+            scenario
+                .instructions
+                .iter()
+                .any(|mutation| mutation.mutagen.mutagen.to_string() == "misses a required property")
+        })
+        .map(|scenario| scenario.request().body().clone())
+        .collect();
+    assert_eq!(missing_properties.len(), 2);
+    assert!(missing_properties.contains(&r#"{"code":"Dae","decimals":1,"symbol":"$"}"#.to_string()));
+    assert!(missing_properties.contains(&r#"{"decimals":1,"name":"Dollar","symbol":"$"}"#.to_string()));
+}
+
+#[test]
+fn header_params_are_sent_as_headers() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        true,
+        "/currencies".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    let request = scenarios[0].request();
+    assert_eq!(request.uri(), "/currencies");
+    assert_eq!(request.headers()["X-Tenant"], "0b2a7a4f-tenant");
+    assert_eq!(request.headers()["Accept"], "application/json");
+
+    assert!(scenarios.iter().any(|scenario| {
+        scenario.expectation().status_code == 422
+            && !scenario.request().headers().contains_key("X-Tenant")
+    }));
+}
+
+#[test]
+fn cookie_params_are_sent_in_the_cookie_header() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        true,
+        "/currencies".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    let request = scenarios[0].request();
+    assert_eq!(request.uri(), "/currencies");
+    assert_eq!(request.headers()["Cookie"], "session=d41d8cd98f");
+
+    let cookies: Vec<String> = scenarios
+        .iter()
+        .filter(|scenario| scenario.expectation().status_code == 422)
+        .map(|scenario| {
+            scenario
+                .request()
+                .headers()
+                .get("Cookie")
+                .map(|cookie| cookie.to_str().unwrap().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    assert!(cookies.contains(&"".to_string()));
+    assert!(cookies.contains(&"session=Daedalus \"malformed\", cookie".to_string()));
+    assert!(cookies.contains(&"theme=NotInAnyEnum; session=d41d8cd98f".to_string()));
+}
+
+#[test]
+fn number_params_mutations() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/prices".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+
+    assert_eq!(scenarios[0].request().uri(), "/prices?amount=1");
+    let failing: Vec<String> = scenarios
+        .iter()
+        .filter(|scenario| scenario.expectation().status_code == 422)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+    assert!(failing.contains(&"/prices?amount=-1".to_string()));
+    assert!(failing.contains(&"/prices?amount=101".to_string()));
+    assert!(failing.contains(&"/prices?amount=1.25".to_string()));
+    assert!(failing.contains(&"/prices?amount=NaN".to_string()));
+    assert!(failing.contains(&"/prices?amount=NotANumber".to_string()));
+}
+
+#[test]
+fn not_multiple_numbers_stay_within_the_limits() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/discounts".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let failing: Vec<String> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 422)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+
+    // The proper value is the maximum, going over it would be a second error
+    assert!(failing.contains(&"/discounts?rate=0.5".to_string()));
+    assert!(!failing.contains(&"/discounts?rate=1.5".to_string()));
+}
+
+#[test]
+fn array_params_follow_their_style() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/rates".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+    let uris = |status: u16| -> Vec<String> {
+        scenarios
+            .iter()
+            .filter(|scenario| scenario.expectation().status_code == status)
+            .map(|scenario| scenario.request().uri().to_string())
+            .collect()
+    };
+
+    assert_eq!(scenarios[0].request().uri(), "/rates?ids=1");
+    let passing = uris(200);
+    assert!(passing.contains(&"/rates?ids=1&ids=2&ids=3".to_string()));
+    assert!(passing.contains(&"/rates?codes=EUR&ids=1".to_string()));
+    assert!(passing.contains(&"/rates?tags=Daedalus%20Daedalus&ids=1".to_string()));
+
+    let failing = uris(422);
+    assert!(failing.contains(&"/rates?ids=1&ids=2&ids=3&ids=4".to_string()));
+    assert!(failing.contains(&"/rates?ids=1&ids=1".to_string()));
+    assert!(failing.contains(&"/rates?ids=NotANumber".to_string()));
+    assert!(failing.contains(&"/rates?codes=NotInAnyEnum&ids=1".to_string()));
+    assert!(failing.contains(&"/rates?tags=Daedalus&ids=1".to_string()));
+}
+
+#[test]
+fn object_params_follow_their_style() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/searches".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+    let uris = |status: u16| -> Vec<String> {
+        scenarios
+            .iter()
+            .filter(|scenario| scenario.expectation().status_code == status)
+            .map(|scenario| scenario.request().uri().to_string())
+            .collect()
+    };
+
+    assert_eq!(scenarios[0].request().uri(), "/searches?filter[limit]=1&filter[status]=active");
+    let passing = uris(200);
+    assert!(passing.contains(&"/searches?range=from,1,to,1&filter[limit]=1&filter[status]=active".to_string()));
+
+    let failing = uris(422);
+    assert!(failing.contains(&"/searches?filter[limit]=1".to_string()));
+    assert!(failing.contains(&"/searches?filter[limit]=NotANumber&filter[status]=active".to_string()));
+    assert!(failing.contains(&"/searches?filter[limit]=1&filter[status]=NotInAnyEnum".to_string()));
+    assert!(failing.contains(
+        &"/searches?filter[limit]=1&filter[status]=active&filter[daedalusUnexpectedProperty]=Daedalus".to_string()
+    ));
+}
+
+#[test]
+fn string_params_follow_their_pattern() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/prices".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+    let pattern = regex::Regex::new(r"^/prices\?currency=[A-Z]{3}&amount=1$").unwrap();
+
+    assert!(scenarios.iter().any(|scenario| {
+        scenario.expectation().status_code == 200
+            && pattern.is_match(&scenario.request().uri().to_string())
+    }));
+    assert!(scenarios.iter().any(|scenario| {
+        scenario.expectation().status_code == 422
+            && scenario.request().uri() == "/prices?currency=!&amount=1"
+    }));
+}
+
+#[test]
+fn string_params_with_formats() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/contacts".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+    let uris = |status: u16| -> Vec<String> {
+        scenarios
+            .iter()
+            .filter(|scenario| scenario.expectation().status_code == status)
+            .map(|scenario| scenario.request().uri().to_string())
+            .collect()
+    };
+
+    let passing = uris(200);
+    assert!(passing.contains(&"/contacts?email=daedalus@example.com".to_string()));
+    assert!(passing.contains(&"/contacts?avatar=RGFlZGFsdXM%3D".to_string()));
+    assert!(passing.contains(&"/contacts?birthday=2019-11-28".to_string()));
+    assert!(passing.contains(&"/contacts?color=PLAIN_STRING_UNKNOWN".to_string()));
+    assert!(passing.iter().any(|uri| {
+        matches!(uri.strip_prefix("/contacts?contact_id="), Some(id) if uuid::Uuid::parse_str(id).is_ok())
+    }));
+
+    let failing = uris(422);
+    assert!(failing.contains(&"/contacts?email=daedalus.example.com".to_string()));
+    assert!(failing.contains(&"/contacts?server=2001:db8:::1:zz".to_string()));
+    assert!(!failing.iter().any(|uri| uri.contains("secret") || uri.contains("color")));
+}
+
+#[test]
+fn string_path_params_with_formats() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/events".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario>  = generator.scenarios().collect();
+    let not_found: Vec<String> = scenarios
+        .iter()
+        .filter(|scenario| scenario.expectation().status_code == 404)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+
+    assert_eq!(scenarios[0].expectation().status_code, 200);
+    assert!(not_found.iter().any(|uri| uri.starts_with("/events/2019-11-28T25:61:00Z/sources/")));
+    assert!(not_found.iter().any(|uri| uri.ends_with("/sources/not:a:uri")));
+}
+
+#[test]
+fn enum_params_with_all_values() {
+    let uris = |all_enum_values: bool, status: u16| -> Vec<String> {
+        let mut config = GeneratorConfig::new(
+            support_file("test_params_openapi.yaml"),
+            None,
+            true,
+            "/orders".to_string(),
+        );
+        config.all_enum_values(all_enum_values);
+        let generator = Generator::new(&config).unwrap();
+        generator
+            .scenarios()
+            .filter(|scenario| scenario.expectation().status_code == status)
+            .map(|scenario| scenario.request().uri().to_string())
+            .collect()
+    };
+
+    let passing = uris(false, 200);
+    assert!(passing.contains(&"/orders?priority=1".to_string()));
+    assert!(!passing.iter().any(|uri| uri.contains("priority=5")));
+    assert!(!passing.iter().any(|uri| uri.contains("state=paid")));
+
+    let passing = uris(true, 200);
+    let values = [
+        "state=open",
+        "state=paid",
+        "state=shipped",
+        "priority=1",
+        "priority=2",
+        "priority=5",
+    ];
+    for value in &values {
+        assert!(passing.iter().any(|uri| uri.ends_with(value)), "{} not used", value);
+    }
+    // Each scenario changes a single parameter
+    assert!(!passing.iter().any(|uri| uri.contains("state=paid&priority=2")));
+
+    let failing = uris(true, 422);
+    assert!(failing.contains(&"/orders?priority=6".to_string()));
+    assert!(failing.contains(&"/orders?state=NotInAnyEnum".to_string()));
+}
+
+#[test]
+fn integer_enum_with_the_largest_integer() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/batches".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let failing: Vec<String> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 422)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+
+    // Nothing is past the largest integer, so the value before the smallest is used
+    assert!(failing.contains(&"/batches?size=99".to_string()));
+    assert!(!failing.iter().any(|uri| uri.contains("size=9223372036854775807")));
+}
+
+#[test]
+fn params_use_spec_examples_and_defaults() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/invoices".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let passing: Vec<String> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 200)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+
+    let invoice = "/invoices/5b6d3c0e-6fd2-4b9c-9a56-8f5d1a2c9e11";
+    assert!(passing.contains(&invoice.to_string()));
+    assert!(passing.contains(&format!("{}?lines=3", invoice)));
+    assert!(passing.contains(&format!("{}?currency=EUR", invoice)));
+    assert!(passing.contains(&format!("{}?rounding=half_up", invoice)));
+    // The example replaces the made up value
+    assert!(!passing.iter().any(|uri| uri.contains("currency=Daedalus")));
+}
+
+#[test]
+fn expected_status_codes_from_the_conversions_file() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_expectations.yaml")),
+        true,
+        "/prices".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+    let status = |uri: &str| -> Vec<u16> {
+        scenarios
+            .iter()
+            .filter(|scenario| scenario.request().uri() == uri)
+            .map(|scenario| scenario.expectation().status_code.as_u16())
+            .collect()
+    };
+
+    // Configured for all the paths
+    assert!(status("/prices?amount=1").contains(&415));
+    assert!(!status("/prices?amount=1").contains(&406));
+    assert_eq!(status("/prices?amount=NaN"), vec![400]);
+    // Missing required parameters are parameters too
+    assert_eq!(status("/prices"), vec![400]);
+    // The mutagen configured for this path wins
+    assert_eq!(status("/prices?amount=NotANumber"), vec![422]);
+}
+
+#[test]
+fn unknown_expectations_are_an_error() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_wrong_expectations.yaml")),
+        true,
+        "/prices".to_string(),
+    );
+    assert!(Generator::new(&config).is_err());
+}
+
+struct QuoteProvider;
+
+impl MutationProvider for QuoteProvider {
+    fn param_mutations(
+        &self,
+        _endpoint: &Endpoint,
+        param: &openapiv3::Parameter,
+    ) -> Vec<Mutation> {
+        let instruction = MutagenInstruction::new(
+            RequestPart::AnyParam,
+            Mutagen::Custom("contains a quote".to_string()),
+            http::StatusCode::BAD_REQUEST,
+        );
+        vec![Mutation::for_param(instruction, param, "'")]
+    }
+}
+
+// Parameter mutations marked with parts of the request which are not parameters
+struct OtherPartsProvider;
+
+impl MutationProvider for OtherPartsProvider {
+    fn param_mutations(
+        &self,
+        _endpoint: &Endpoint,
+        param: &openapiv3::Parameter,
+    ) -> Vec<Mutation> {
+        [RequestPart::Body, RequestPart::Endpoint]
+            .iter()
+            .map(|part| {
+                let instruction = MutagenInstruction::new(
+                    part.clone(),
+                    Mutagen::Custom("contains an x".to_string()),
+                    http::StatusCode::UNPROCESSABLE_ENTITY,
+                );
+                Mutation::for_param(instruction, param, "x")
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn registered_providers_add_mutations() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/prices".to_string(),
+    );
+    let mut generator = Generator::new(&config).unwrap();
+    generator.register(QuoteProvider);
+    let scenarios: Vec<Scenario> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 400)
+        .collect();
+
+    let uris: Vec<String> = scenarios
+        .iter()
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+    assert_eq!(uris.len(), 2);
+    assert!(uris.contains(&"/prices?amount='".to_string()));
+    assert!(uris.iter().any(|uri| uri.contains("currency='")));
+    let quote = Mutagen::Custom("contains a quote".to_string());
+    assert!(scenarios[0]
+        .instructions
+        .iter()
+        .any(|instruction| instruction.mutagen.mutagen == quote));
+}
+
+fn passing_order_uris(strategy: &str) -> Vec<String> {
+    let mut config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/orders".to_string(),
+    );
+    config
+        .all_enum_values(true)
+        .combination(strategy.parse().unwrap());
+    let generator = Generator::new(&config).unwrap();
+    generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 200)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect()
+}
+
+#[test]
+fn combination_strategies() {
+    let with_both = |uris: &[String], state: &str, priority: &str| {
+        uris.iter()
+            .any(|uri| uri.contains(state) && uri.contains(priority))
+    };
+
+    let one_at_a_time = passing_order_uris("one-at-a-time");
+    assert_eq!(one_at_a_time.len(), 7);
+    assert!(!with_both(&one_at_a_time, "state=paid", "priority=2"));
+
+    let pairwise = passing_order_uris("pairwise");
+    for state in &["state=open", "state=paid", "state=shipped"] {
+        for priority in &["priority=1", "priority=2", "priority=5"] {
+            assert!(with_both(&pairwise, state, priority));
+        }
+    }
+
+    // 4 values for each parameter, including not sending it
+    assert_eq!(passing_order_uris("cartesian").len(), 16);
+    assert!(passing_order_uris("cartesian:3").len() < 16);
+
+    assert!("sideways".parse::<CombinationStrategy>().is_err());
+}
+
+#[test]
+fn the_same_seed_generates_the_same_scenarios() {
+    let requests = |seed: u64| -> Vec<String> {
+        let mut config = GeneratorConfig::new(
+            support_file("test_params_openapi.yaml"),
+            None,
+            true,
+            "/prices".to_string(),
+        );
+        config.seed(seed);
+        let generator = Generator::new(&config).unwrap();
+        assert_eq!(generator.seed(), seed);
+        generator
+            .scenarios()
+            .map(|scenario| {
+                let request = scenario.request();
+                format!("{} {} {}", request.method(), request.uri(), request.body())
+            })
+            .collect()
+    };
+
+    assert_eq!(requests(42), requests(42));
+    // Strings following a pattern are random
+    assert_ne!(requests(42), requests(43));
+}
+
+#[test]
+fn scenarios_have_stable_ids() {
+    let ids = |seed: u64| -> Vec<String> {
+        let mut config = GeneratorConfig::new(
+            support_file("test_params_openapi.yaml"),
+            None,
+            true,
+            "/".to_string(),
+        );
+        config.seed(seed);
+        let generator = Generator::new(&config).unwrap();
+        generator
+            .scenarios()
+            .map(|scenario| scenario.id().to_string())
+            .collect()
+    };
+
+    let first = ids(1);
+    let mut unique = first.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), first.len());
+    // Random values do not change the ids
+    assert_eq!(first, ids(2));
+}
+
+#[test]
+fn scenarios_can_be_written_and_read_back() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_conversions.yaml")),
+        true,
+        "/currencies".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+    let describe = |scenario: &Scenario| -> String {
+        let request = scenario.request();
+        // The order of the headers does not matter
+        let headers: std::collections::BTreeMap<String, String> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_string()))
+            .collect();
+        let instructions: Vec<String> = scenario
+            .instructions
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+        format!(
+            "{} {:?} {} {} {:?} {:?} {} {}",
+            scenario.id(),
+            scenario.server_origin(),
+            request.method(),
+            request.uri(),
+            headers,
+            request.body(),
+            scenario.expectation().status_code,
+            instructions.join(",")
+        )
+    };
+
+    for extension in &["yaml", "json"] {
+        let mut file = std::env::temp_dir();
+        file.push(format!("daedalus_scenarios.{}", extension));
+        let filename = file.to_str().unwrap();
+
+        write_scenarios(&scenarios, filename).unwrap();
+        let read = read_scenarios(filename).unwrap();
+        assert_eq!(read.len(), scenarios.len());
+        for (original, read) in scenarios.iter().zip(read.iter()) {
+            assert_eq!(describe(original), describe(read));
+            assert_eq!(
+                original.expectation().body.is_some(),
+                read.expectation().body.is_some()
+            );
+        }
+    }
+}
+
+#[test]
+fn servers_can_be_selected_and_their_variables_set() {
+    let paths = |config: &GeneratorConfig| -> Vec<String> {
+        let generator = Generator::new(config).unwrap();
+        let mut paths: Vec<String> = generator
+            .scenarios()
+            .map(|scenario| format!("{} {}", scenario.request().method(), scenario.request().uri()))
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    };
+    let mut config = GeneratorConfig::new(
+        support_file("test_servers_openapi.yaml"),
+        None,
+        false,
+        "/".to_string(),
+    );
+
+    // The first server by default, paths and operations can have their own servers
+    let generator = Generator::new(&config).unwrap();
+    assert_eq!(generator.server_origin(), Some("http://localhost:3000"));
+    assert_eq!(
+        paths(&config),
+        vec!["GET /api/carriers", "GET /v1/shipments", "POST /admin/carriers"]
+    );
+    // Their origin too, relative URLs keep the one of the servers around them
+    let origin = |uri: &str| -> Option<String> {
+        generator
+            .scenarios()
+            .find(|scenario| scenario.request().uri() == uri)
+            .and_then(|scenario| scenario.server_origin().map(String::from))
+    };
+    assert_eq!(origin("/v1/shipments").as_deref(), Some("http://localhost:3000"));
+    assert_eq!(origin("/api/carriers").as_deref(), Some("https://carriers.example.com"));
+    assert_eq!(origin("/admin/carriers").as_deref(), Some("https://carriers.example.com"));
+
+    config.server(ServerSelection::Description("staging".to_string()));
+    let generator = Generator::new(&config).unwrap();
+    assert_eq!(generator.server_origin(), Some("https://staging.example.com"));
+    assert!(paths(&config).contains(&"GET /v2/shipments".to_string()));
+
+    config.server("1".parse().unwrap());
+    config.server_variable("environment", "sandbox");
+    config.server_variable("version", "v3");
+    let generator = Generator::new(&config).unwrap();
+    assert_eq!(generator.server_origin(), Some("https://sandbox.example.com"));
+    assert!(paths(&config).contains(&"GET /v3/shipments".to_string()));
+
+    // Values outside of the enumeration and unknown servers are errors
+    config.server_variable("environment", "production");
+    assert!(Generator::new(&config).is_err());
+    config.server_variable("environment", "sandbox");
+    config.server(ServerSelection::Index(5));
+    assert!(Generator::new(&config).is_err());
+}
+
+#[test]
+fn operations_can_be_filtered() {
+    let operations = |config: &GeneratorConfig| -> Vec<String> {
+        let generator = Generator::new(config).unwrap();
+        let mut operations: Vec<String> = generator
+            .scenarios()
+            .map(|scenario| format!("{} {}", scenario.request().method(), scenario.request().uri()))
+            .collect();
+        operations.sort();
+        operations.dedup();
+        operations
+    };
+    let config = |matches: &str| {
+        GeneratorConfig::new(
+            support_file("test_servers_openapi.yaml"),
+            None,
+            false,
+            matches.to_string(),
+        )
+    };
+
+    // Paths are matched with regular expressions
+    assert_eq!(operations(&config("^/ship")), vec!["GET /v1/shipments"]);
+    assert_eq!(operations(&config("s$")).len(), 3);
+    assert!(Generator::new(&config("/(")).is_err());
+
+    let mut excluding = config("/");
+    excluding.exclude("carrier");
+    assert_eq!(operations(&excluding), vec!["GET /v1/shipments"]);
+
+    let mut by_method = config("/");
+    by_method.method("post");
+    assert_eq!(operations(&by_method), vec!["POST /admin/carriers"]);
+
+    let mut by_operation_id = config("/");
+    by_operation_id.operation_id(r"\.index$");
+    assert_eq!(
+        operations(&by_operation_id),
+        vec!["GET /api/carriers", "GET /v1/shipments"]
+    );
+
+    // The filters are combined
+    let mut by_tag = config("/");
+    by_tag.tag("carriers").method("GET");
+    assert_eq!(operations(&by_tag), vec!["GET /api/carriers"]);
+    by_tag.exclude("carriers");
+    assert!(operations(&by_tag).is_empty());
+}
+
+#[test]
+fn references_to_other_files_are_resolved() {
+    let config = GeneratorConfig::new(
+        support_file("external/openapi.yaml"),
+        None,
+        true,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+    let requests: Vec<String> = scenarios
+        .iter()
+        .map(|scenario| {
+            let request = scenario.request();
+            format!("{} {} {}", request.method(), request.uri(), request.body())
+        })
+        .collect();
+
+    // Parameters, path items and schemas from other files, with their own references resolved
+    assert!(requests.iter().any(|r| r.starts_with("GET /pets?limit=101")));
+    assert!(requests.iter().any(|r| r.starts_with("GET /pets?kind=NotInAnyEnum")));
+    assert!(requests
+        .iter()
+        .any(|r| r.starts_with("POST /pets") && r.contains(r#""kind":"cat""#)));
+    assert!(requests.iter().any(|r| r.starts_with("GET /pets/")));
+}
+
+#[test]
+fn unresolvable_and_cyclic_references_are_errors() {
+    let error = |filename: &str| -> String {
+        let config = GeneratorConfig::new(support_file(filename), None, true, "/".to_string());
+        match Generator::new(&config) {
+            Ok(_) => panic!("{} should not be read", filename),
+            Err(error) => error.to_string(),
+        }
+    };
+
+    let unresolved = error("external/unresolved.yaml");
+    assert!(unresolved.contains("`/Dog`"));
+    assert!(unresolved.contains("pet.yaml"));
+
+    // References which only point to other references never reach a schema
+    let cycle = error("external/cycle.yaml");
+    assert!(cycle.contains("cycle"));
+    assert!(cycle.contains("loop.yaml#/Loop -> "));
+}
+
+#[test]
+fn recursive_schemas_in_other_files_are_read() {
+    let config = GeneratorConfig::new(
+        support_file("external/tree.yaml"),
+        None,
+        true,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+
+    let create = scenarios
+        .iter()
+        .find(|scenario| scenario.expectation().status_code == 201)
+        .unwrap();
+    assert!(create.request().body().contains(r#""name":"#));
+    let list = scenarios
+        .iter()
+        .find(|scenario| scenario.expectation().status_code == 200)
+        .unwrap();
+    assert!(list.expectation().body.is_some());
+}
+
+#[test]
+fn openapi_31_documents_are_read() {
+    let mut config = GeneratorConfig::new(
+        support_file("test_openapi31.yaml"),
+        None,
+        true,
+        "/".to_string(),
+    );
+    config.all_enum_values(true);
+    let generator = Generator::new(&config).unwrap();
+    let requests: Vec<String> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code.is_success())
+        .map(|scenario| {
+            let request = scenario.request();
+            format!("{} {} {}", request.method(), request.uri(), request.body())
+        })
+        .collect();
+
+    // `const`, `$defs`, recursive ones too, and `examples` arrays
+    assert!(requests.contains(&"GET /reports?kind=daily ".to_string()));
+    assert!(requests.iter().any(|r| r.contains("format=csv")));
+    assert!(requests.iter().any(|r| r.contains("limit=20")));
+    // `type: [string, "null"]` is a nullable string
+    assert!(requests.iter().any(|r| r.starts_with("POST /reports")
+        && r.contains(r#""note":"Daedalus""#)
+        && r.contains(r#""title":"Monthly sales""#)));
+}
+
+#[test]
+fn missing_openapi_31_definitions_are_errors() {
+    let config = GeneratorConfig::new(
+        support_file("test_openapi31_missing.yaml"),
+        None,
+        true,
+        "/".to_string(),
+    );
+    let error = match Generator::new(&config) {
+        Ok(_) => panic!("test_openapi31_missing.yaml should not be read"),
+        Err(error) => error.to_string(),
+    };
+
+    assert!(error.contains("`/components/schemas/Report/$defs/Missing`"));
+    assert!(error.contains("test_openapi31_missing.yaml"));
+}
+
+#[test]
+fn swagger_2_documents_are_converted() {
+    let config = GeneratorConfig::new(
+        support_file("test_swagger2.yaml"),
+        None,
+        true,
+        "/".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    // The host, scheme and base path are the server
+    assert_eq!(generator.server_origin(), Some("https://pets.example.com"));
+
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+    let requests: Vec<String> = scenarios
+        .iter()
+        .map(|scenario| {
+            let request = scenario.request();
+            format!(
+                "{} {} {} {}",
+                scenario.expectation().status_code.as_u16(),
+                request.method(),
+                request.uri(),
+                request.body()
+            )
+        })
+        .collect();
+
+    // Parameters have their types in their schemas
+    assert!(requests.contains(&"200 GET /v1/pets?kinds=cat ".to_string()));
+    assert!(requests.contains(&"422 GET /v1/pets?limit=101 ".to_string()));
+    assert!(scenarios.iter().any(|scenario| {
+        let request = scenario.request();
+        let source = request.headers().get("X-Request-Source");
+        source.map(|value| value.to_str().unwrap()) == Some("minos")
+    }));
+    // The body parameter is the request body
+    assert!(requests.contains(&r#"201 POST /v1/pets {"name":"Daedalus","tag":"Daedalus"}"#.to_string()));
+    // Responses have their schemas in their content
+    let not_found = scenarios
+        .iter()
+        .find(|scenario| {
+            scenario.expectation().status_code == 404 && scenario.request().method() == "GET"
+        })
+        .unwrap();
+    let response = not_found.expectation().body.as_ref().unwrap();
+    assert!(response.content.contains_key("application/json"));
+}
+
+#[test]
+fn security_schemes_credentials_are_checked() {
+    let config = GeneratorConfig::new(
+        support_file("test_security_openapi.yaml"),
+        Some(support_file("test_security_conversions.yaml")),
+        true,
+        "/".to_string(),
+    );
+    let scenarios: Vec<Scenario> = Generator::new(&config).unwrap().scenarios().collect();
+    let header = |scenario: &Scenario, name: &str| {
+        let request = scenario.request();
+        request
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_string())
+            .unwrap_or_default()
+    };
+    let requests: Vec<String> = scenarios
+        .iter()
+        .map(|scenario| {
+            format!(
+                "{} {} {}{}",
+                scenario.expectation().status_code.as_u16(),
+                scenario.request().uri(),
+                header(scenario, "Authorization"),
+                header(scenario, "X-API-Key")
+            )
+        })
+        .collect();
+
+    // The security of the spec applies to the operations without their own
+    assert!(requests.contains(&"200 /pets Bearer a-token".to_string()));
+    assert!(requests.contains(&"401 /pets ".to_string()));
+    assert!(requests.contains(&"401 /pets Bearer Daedalus-malformed-credentials".to_string()));
+    assert!(requests.contains(&"401 /pets Basic YS10b2tlbg==".to_string()));
+    // Basic credentials are encoded, API keys go where the scheme says
+    assert!(requests.contains(&"200 /admin Basic ZGFlZGFsdXM6c2VjcmV0".to_string()));
+    assert!(requests.contains(&"401 /admin Bearer daedalus:secret".to_string()));
+    assert!(requests.contains(&"200 /reports the-key".to_string()));
+    assert!(requests.contains(&"401 /reports Daedalus-malformed-credentials".to_string()));
+    assert!(requests.contains(&"401 /reports Bearer the-key".to_string()));
+    // Basic is accepted too, it is not a wrong scheme
+    assert!(!requests.iter().any(|request| request.starts_with("401 /legacy Basic")));
+    // Public operations pass without credentials
+    assert!(requests.contains(&"200 /health ".to_string()));
+    assert!(!requests.iter().any(|request| request.starts_with("401 /health")));
+    // With optional credentials, the known ones pass too
+    assert!(requests.contains(&"200 /profile ".to_string()));
+    assert!(requests.contains(&"200 /profile the-key".to_string()));
+    assert!(!requests.iter().any(|request| request.starts_with("401 /profile")));
+    // Without credentials in the conversions file they are not checked
+    assert!(!requests.iter().any(|request| request.starts_with("401 /partners")));
+
+    let public = scenarios
+        .iter()
+        .find(|scenario| scenario.request().uri() == "/health")
+        .unwrap();
+    assert!(public.without_valid_credentials());
+    let authenticated = scenarios
+        .iter()
+        .find(|scenario| header(scenario, "Authorization") == "Bearer a-token")
+        .unwrap();
+    assert!(!authenticated.without_valid_credentials());
+}
+
+#[test]
+fn expected_status_codes_only_change_failing_mutations() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        Some(support_file("test_none_expectations.yaml")),
+        true,
+        "/prices".to_string(),
+    );
+    let generator = Generator::new(&config).unwrap();
+    let scenarios: Vec<Scenario> = generator.scenarios().collect();
+    let status = |uri: &str| -> Vec<u16> {
+        scenarios
+            .iter()
+            .filter(|scenario| scenario.request().uri() == uri)
+            .map(|scenario| scenario.expectation().status_code.as_u16())
+            .collect()
+    };
+
+    // Without the optional currency it still passes
+    assert!(status("/prices?amount=1").contains(&200));
+    // Without the required amount it fails with the configured status
+    assert_eq!(status("/prices"), vec![400]);
+}
+
+#[test]
+fn provider_mutations_send_their_params_whatever_their_request_part() {
+    let config = GeneratorConfig::new(
+        support_file("test_params_openapi.yaml"),
+        None,
+        true,
+        "/prices".to_string(),
+    );
+    let mut generator = Generator::new(&config).unwrap();
+    generator.register(OtherPartsProvider);
+    let uris: Vec<String> = generator
+        .scenarios()
+        .filter(|scenario| scenario.expectation().status_code == 422)
+        .map(|scenario| scenario.request().uri().to_string())
+        .collect();
+    assert!(uris.contains(&"/prices?amount=x".to_string()));
+    assert!(uris.iter().any(|uri| uri.contains("currency=x")));
 }
@@ -16,10 +16,10 @@ impl Authentication {
         };
         Authentication { mauth_info: auth }
     }
-    pub fn authenticate(&self, mut requ: &mut hyper::Request<hyper::Body>) {
+    pub fn authenticate(&self, mut requ: &mut hyper::Request<hyper::Body>, body: &str) {
         if let Some(mauth_info) = &self.mauth_info {
-            // on empty body we digest "" TODO: Support request bodies
-            let (_, body_digest) = MAuthInfo::build_body_with_digest("".to_string());
+            // on empty body we digest ""
+            let (_, body_digest) = MAuthInfo::build_body_with_digest(body.to_string());
             mauth_info.sign_request_v2(&mut requ, &body_digest);
             mauth_info.sign_request_v1(&mut requ, &body_digest);
         }
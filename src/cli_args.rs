@@ -1,4 +1,5 @@
 //use structopt::StructOpt;
+use crate::service;
 use clap::Clap;
 
 pub fn config() -> CLIArgs {
@@ -6,13 +7,67 @@ pub fn config() -> CLIArgs {
 }
 
 impl CLIArgs {
+    // The scenarios selected by the user, all of them when None
+    pub fn scenario_ids(&self) -> Option<Vec<String>> {
+        self.scenario_ids.as_ref().map(|ids| {
+            ids.split(',')
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect()
+        })
+    }
+
+    // The URL given by the user, or localhost when no server of the openapi file is selected.
+    // None to send each scenario to the server of its operation.
+    pub fn base_url(&self) -> Option<String> {
+        match (&self.base_url, &self.server) {
+            (Some(url), _) => Some(url.clone()),
+            (None, Some(_)) => None,
+            (None, None) => Some(String::from(service::LOCALHOST)),
+        }
+    }
+
     pub fn generator_config(&self) -> daedalus::GeneratorConfig {
-        daedalus::GeneratorConfig::new(
+        let mut config = daedalus::GeneratorConfig::new(
             self.filename.clone(),
             self.conv_filename.clone(),
             self.scenarios_all_codes,
             self.matches.clone(),
-        )
+        );
+        config.path_error_status(self.path_error_status);
+        for exclude in &self.excludes {
+            config.exclude(exclude);
+        }
+        for method in &self.methods {
+            config.method(method);
+        }
+        for operation_id in &self.operation_ids {
+            config.operation_id(operation_id);
+        }
+        for tag in &self.tags {
+            config.tag(tag);
+        }
+        config.all_enum_values(self.scenarios_all_enum_values);
+        config.combination(self.combination.clone());
+        if let Some(seed) = self.seed {
+            config.seed(seed);
+        }
+        if let Some(server) = &self.server {
+            config.server(server.clone());
+        }
+        for (name, value) in &self.server_variables {
+            config.server_variable(name, value);
+        }
+        config
+    }
+}
+
+// Server variables are given as name=value
+fn parse_server_variable(variable: &str) -> Result<(String, String), String> {
+    let mut parts = variable.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("`{}` is not in the form name=value", variable)),
     }
 }
 
@@ -28,10 +83,23 @@ pub struct CLIArgs {
     #[clap(
         short = 'u',
         long = "url",
-        about = "URL where the server is running (it can also be in localhost)",
-        default_value = "http://localhost:3000"
+        about = "URL where the server is running (it can also be in localhost). Defaults to the servers selected with --server, each operation uses its own, or to http://localhost:3000"
+    )]
+    pub base_url: Option<String>,
+
+    #[clap(
+        long = "server",
+        about = "Server of the openapi file to use, by its position starting at 0 or by its description. Its base path goes before the paths."
     )]
-    pub base_url: String,
+    pub server: Option<daedalus::ServerSelection>,
+
+    #[clap(
+        long = "server-variable",
+        about = "Value of a variable of the server URLs, as name=value. Can be repeated.",
+        number_of_values = 1,
+        parse(try_from_str = parse_server_variable)
+    )]
+    pub server_variables: Vec<(String, String)>,
 
     #[clap(
         short = 'c',
@@ -43,11 +111,40 @@ pub struct CLIArgs {
     #[clap(
         short = 'm',
         long = "matches",
-        about = "Only generate scenarios for paths matching certain expression.",
+        about = "Only generate scenarios for paths matching this regular expression.",
         default_value = "/"
     )]
     pub matches: String,
 
+    #[clap(
+        short = 'x',
+        long = "exclude",
+        about = "Skip the paths matching this regular expression. Can be repeated.",
+        number_of_values = 1
+    )]
+    pub excludes: Vec<String>,
+
+    #[clap(
+        long = "method",
+        about = "Only generate scenarios for operations with this HTTP method. Can be repeated.",
+        number_of_values = 1
+    )]
+    pub methods: Vec<String>,
+
+    #[clap(
+        long = "operation-id",
+        about = "Only generate scenarios for operations whose operationId matches this regular expression. Can be repeated.",
+        number_of_values = 1
+    )]
+    pub operation_ids: Vec<String>,
+
+    #[clap(
+        long = "tag",
+        about = "Only generate scenarios for operations with this tag. Can be repeated.",
+        number_of_values = 1
+    )]
+    pub tags: Vec<String>,
+
     #[clap(
         short = 'a',
         long = "all-codes",
@@ -55,6 +152,44 @@ pub struct CLIArgs {
     )]
     pub scenarios_all_codes: bool,
 
+    #[clap(
+        long = "path-error-status",
+        about = "Status code expected when a path parameter has a wrong value.",
+        default_value = "404"
+    )]
+    pub path_error_status: http::StatusCode,
+
+    #[clap(
+        long = "all-enum-values",
+        about = "Generate a passing scenario for each value of the enumerations. Default is to use only one value."
+    )]
+    pub scenarios_all_enum_values: bool,
+
+    #[clap(
+        long = "combinations",
+        about = "How the values of the parameters are combined: one-at-a-time, pairwise, cartesian or cartesian:<limit>.",
+        default_value = "one-at-a-time"
+    )]
+    pub combination: daedalus::CombinationStrategy,
+
+    #[clap(
+        long = "seed",
+        about = "Seed for the random values. Use the seed printed by a previous run to repeat its exact requests."
+    )]
+    pub seed: Option<u64>,
+
+    #[clap(
+        long = "scenarios",
+        about = "Comma separated list of scenario IDs. Only these scenarios are run."
+    )]
+    pub scenario_ids: Option<String>,
+
+    #[clap(
+        long = "scenarios-file",
+        about = "Runs the scenarios in this file, written by `ls --output`, instead of generating them from the openapi file."
+    )]
+    pub scenarios_file: Option<String>,
+
     #[clap(subcommand)]
     pub command: Command,
 }
@@ -62,7 +197,14 @@ pub struct CLIArgs {
 #[derive(Clap)]
 pub enum Command {
     #[clap(about = "Shows generated scenarios but does not run them.")]
-    Ls,
+    Ls {
+        #[clap(
+            short = 'o',
+            long = "output",
+            about = "Also writes the scenarios to this file, as JSON if it ends in .json and as YAML otherwise."
+        )]
+        output: Option<String>,
+    },
 
     Performance(PerformanceCommand),
 
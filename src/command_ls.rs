@@ -1,11 +1,30 @@
 use crate::reporter;
+use anyhow::Context;
 use daedalus::Scenario;
 
-pub fn run<'a>(scenarios: impl Iterator<Item = Scenario<'a>>) {
+pub fn run(
+    scenarios: impl Iterator<Item = Scenario>,
+    seed: Option<u64>,
+    output: Option<String>,
+) -> anyhow::Result<()> {
     let mut total = 0;
+    // Scenarios are only kept when they are written
+    let mut listed = Vec::new();
     for scenario in scenarios {
         reporter::print_mutation_scenario(&scenario);
         total += 1;
+        if output.is_some() {
+            listed.push(scenario);
+        }
     }
-    println!("{:?} scenarios generated.", total);
+    match seed {
+        Some(seed) => println!("{:?} scenarios generated with seed {}.", total, seed),
+        None => println!("{:?} scenarios read.", total),
+    }
+    if let Some(filename) = output {
+        daedalus::write_scenarios(&listed, &filename)
+            .context("Impossible to write the scenarios")?;
+        println!("Scenarios written to {}.", filename);
+    }
+    Ok(())
 }
@@ -12,12 +12,12 @@ use std::{thread, time};
 //use std::sync::Arc;
 
 lazy_static! {
-    static ref PATH_HEADER_LIST: RwLock<Vec<(reqwest::Url, HeaderMap<HeaderValue>)>> =
+    static ref PATH_HEADER_LIST: RwLock<Vec<(reqwest::Method, reqwest::Url, HeaderMap<HeaderValue>, String)>> =
         RwLock::new(Vec::new());
 }
 
-pub fn run<'a>(
-    scenarios: impl Iterator<Item = Scenario<'a>>,
+pub fn run(
+    scenarios: impl Iterator<Item = Scenario>,
     service: &service::Service,
     command: PerformanceCommand,
 ) {
@@ -33,9 +33,11 @@ pub fn run<'a>(
         // Need to drop the rwlock after this block so we can read it
         let mut req_list = PATH_HEADER_LIST.write().unwrap();
         for scenario in scenarios {
-            let request = service.runnable_request(scenario.request()).http_request();
+            let runnable = service.runnable_request(&scenario);
+            let body = runnable.body().to_string();
+            let request = runnable.http_request();
             let url = reqwest::Url::parse(&request.uri().to_string()).unwrap();
-            req_list.push((url, request.headers().clone()));
+            req_list.push((request.method().clone(), url, request.headers().clone(), body));
         }
         if req_list.is_empty() {
             println!("There are 0 scenarios to run performance testing. Consider using a conversions file.");
@@ -116,8 +118,9 @@ async fn call_url(user: &GooseUser) -> GooseTaskResult {
             .client
             .lock()
             .await
-            .get(path_header.0)
-            .headers(path_header.1);
+            .request(path_header.0, path_header.1)
+            .headers(path_header.2)
+            .body(path_header.3);
         user.goose_send(builder, None).await?;
     }
     Ok(())
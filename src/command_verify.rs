@@ -5,29 +5,29 @@ use daedalus::Scenario;
 use std::time::Instant;
 use tracing::debug;
 
-pub fn run<'a>(
-    scenarios: impl Iterator<Item = Scenario<'a>>,
+pub fn run(
+    scenarios: impl Iterator<Item = Scenario>,
     service: &service::Service,
     allow_missing_rs: bool,
+    seed: Option<u64>,
 ) {
     let rt = tokio::runtime::Runtime::new().unwrap();
     let start = Instant::now();
     let results = rt.block_on(run_testing_scenarios(scenarios, &service, allow_missing_rs));
-    reporter::run_summary(&results, start);
+    reporter::run_summary(&results, start, seed);
 }
 
-async fn run_testing_scenarios<'a>(
-    scenarios: impl Iterator<Item = Scenario<'a>>,
+async fn run_testing_scenarios(
+    scenarios: impl Iterator<Item = Scenario>,
     service: &service::Service,
     allow_missing_rs: bool,
 ) -> Vec<(String, bool)> {
     let mut results = Vec::new();
 
     for scenario in scenarios {
-        let request = scenario.request();
-        let path = request.uri().path().to_owned();
+        let path = scenario.request().uri().path().to_owned();
 
-        let runnable = service.runnable_request(request);
+        let runnable = service.runnable_request(&scenario);
         reporter::print_runnable_scenario(&scenario, &runnable);
         let response = service.send(runnable).await;
         match response {
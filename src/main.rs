@@ -13,7 +13,7 @@ mod validator;
 
 use crate::cli_args::Command;
 use crate::service::Service;
-use daedalus::Generator;
+use daedalus::{Generator, Scenario};
 use anyhow::{Context, Result};
 use tracing_subscriber::filter::EnvFilter;
 use tracing_subscriber::FmtSubscriber;
@@ -35,16 +35,35 @@ fn main() -> Result<()>{
         .init();
 
     let config = cli_args::config();
-    let service = Service::new(&config.base_url);
 
-    let generator = Generator::new(&config.generator_config()).context("Impossible to generate scenarios")?;
-    let scenarios = generator.scenarios();
+    // Generated scenarios are created lazily. Scenarios read from a file have no seed to repeat them
+    let generator;
+    let (scenarios, seed): (Box<dyn Iterator<Item = Scenario>>, _) = match &config.scenarios_file {
+        Some(filename) => {
+            let scenarios = daedalus::read_scenarios(filename).context("Impossible to read the scenarios")?;
+            (Box::new(scenarios.into_iter()), None)
+        }
+        None => {
+            generator = Generator::new(&config.generator_config()).context("Impossible to generate scenarios")?;
+            (Box::new(generator.scenarios()), Some(generator.seed()))
+        }
+    };
+    // The seed also makes the trace ids of the requests reproducible
+    let service = Service::new(
+        config.base_url(),
+        seed.or(config.seed).unwrap_or_else(rand::random),
+    );
+    let scenario_ids = config.scenario_ids();
+    let scenarios = scenarios.filter(|scenario| match &scenario_ids {
+        Some(ids) => ids.iter().any(|id| id == scenario.id()),
+        None => true,
+    });
 
     println!("{}", LOGO);
     match config.command {
-        Command::Ls => command_ls::run(scenarios),
+        Command::Ls { output } => command_ls::run(scenarios, seed, output)?,
         Command::Performance(config) => command_performance::run(scenarios, &service, config),
-        Command::Verify { without_rs } => command_verify::run(scenarios, &service, without_rs),
+        Command::Verify { without_rs } => command_verify::run(scenarios, &service, without_rs, seed),
     }
     Ok(())
 }
@@ -39,12 +39,12 @@ pub fn print_mutation_scenario(scenario: &Scenario) {
     //  let endpoint = &scenario.endpoint;
     let mutations = &scenario.instructions;
 
-    printer.print_scenario("Scenario:");
+    printer.print_scenario(format!("Scenario {}:", scenario.id()));
     printer.print_scenario(scenario);
     let mut sorted_mutations = mutations.to_owned();
     sorted_mutations.sort(); //_by(|a, b| a.mutagen.expected.cmp(&b.mutagen.expected));
     for mutation in &sorted_mutations {
-        let color = if mutation.mutagen.expected == http::StatusCode::OK {
+        let color = if mutation.mutagen.expected.is_success() {
             LIGHT_BLUE
         } else {
             Color::Blue
@@ -52,10 +52,7 @@ pub fn print_mutation_scenario(scenario: &Scenario) {
         printer.print_color(mutation, color);
     }
     printer.print_color(
-        format!(
-            "  Expects {}",
-            sorted_mutations.pop().unwrap().mutagen.expected
-        ),
+        format!("  Expects {}", scenario.expectation().status_code),
         Color::Blue,
     );
     // printer.print_color(
@@ -66,7 +63,7 @@ pub fn print_mutation_scenario(scenario: &Scenario) {
 
 use itertools::Itertools;
 
-pub fn run_summary(results: &[(String, bool)], start: std::time::Instant) {
+pub fn run_summary(results: &[(String, bool)], start: std::time::Instant, seed: Option<u64>) {
     let failed = results.iter().filter(|&x| !x.1).count();
     let by_path = results.iter().group_by(|x| &x.0);
 
@@ -96,6 +93,10 @@ pub fn run_summary(results: &[(String, bool)], start: std::time::Instant) {
         results.len() - failed,
         failed,
     );
+    // Scenarios read from a file are repeated running the file again
+    if let Some(seed) = seed {
+        println!("Run with `--seed {}` to repeat these requests.", seed);
+    }
 
     if failed > 0 {
         print_error("Some tests have failed.");
@@ -1,34 +1,48 @@
 //use std::fmt;
 //use crate::cli_args::*;
 use crate::authentication::Authentication;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use reqwest::header::{HeaderMap, HeaderValue};
+use std::sync::Mutex;
 use tracing::debug;
 //use reqwest::Method;
-use daedalus::Request;
+use daedalus::Scenario;
+
+// Where the scenarios are sent when there is no other URL
+pub const LOCALHOST: &str = "http://localhost:3000";
 
 #[derive(Debug)]
 pub struct RunnableRequest {
     request: http::request::Request<hyper::Body>,
+    body: String,
 }
 
 impl RunnableRequest {
-    fn new(minos_request: http::request::Request<hyper::Body>) -> Self {
+    fn new(
+        minos_request: http::request::Request<hyper::Body>,
+        body: String,
+        rng: &mut StdRng,
+    ) -> Self {
         let mut request = minos_request; //.http_request();
-        Self::headers(&mut request.headers_mut());
-        RunnableRequest { request }
+        Self::headers(&mut request.headers_mut(), rng);
+        RunnableRequest { request, body }
     }
 
     pub fn http_request(self) -> http::request::Request<hyper::Body> {
         self.request
     }
 
+    // hyper::Body can not be read back, we keep a copy to send it with reqwest
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
     pub fn trace_id(&self) -> &str {
         self.request.headers()["X-B3-TraceID"].to_str().unwrap()
     }
 
-    fn headers(request_headers: &mut HeaderMap<HeaderValue>) {
-        let mut rng = rand::thread_rng();
+    fn headers(request_headers: &mut HeaderMap<HeaderValue>, rng: &mut StdRng) {
         let trace_id = format!("{:x}", rng.gen::<u128>());
         let span_id = format!("{:x}", rng.gen::<u64>());
         // TODO: Verify apps receive and use thsese keys
@@ -39,9 +53,12 @@ impl RunnableRequest {
 }
 
 pub struct Service {
-    base_url: String,
+    // None to use the server of the operation of each scenario
+    base_url: Option<String>,
     client: reqwest::Client, //reqwest::blocking::Client,
     authentication: Authentication,
+    // Trace ids are random too, the seed of the run makes them reproducible
+    rng: Mutex<StdRng>,
 }
 
 pub struct ServiceResponse {
@@ -51,28 +68,37 @@ pub struct ServiceResponse {
 }
 
 impl Service {
-    pub fn new(base_url: &str) -> Self {
+    pub fn new(base_url: Option<String>, seed: u64) -> Self {
         let client = reqwest::Client::new();
         let authentication = Authentication::new();
         Service {
-            base_url: base_url.to_owned(),
+            base_url,
             client,
             authentication,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
         }
     }
-    pub fn runnable_request(&self, minos_request: Request) -> RunnableRequest {
-        let mut request = minos_request;
-        *request.uri_mut() = format!("{}{}", self.base_url, request.uri())
-            .parse()
-            .unwrap();
+    // Scenarios checking the credentials of the operation are sent as they are, without authentication
+    pub fn runnable_request(&self, scenario: &Scenario) -> RunnableRequest {
+        let mut request = scenario.request();
+        let base_url = self
+            .base_url
+            .as_deref()
+            .or_else(|| scenario.server_origin())
+            .unwrap_or(LOCALHOST);
+        *request.uri_mut() = format!("{}{}", base_url, request.uri()).parse().unwrap();
+        let body = request.body().clone();
         let mut request = request.map(hyper::Body::from);
-        self.authentication.authenticate(&mut request);
-        RunnableRequest::new(request)
+        if !scenario.without_valid_credentials() {
+            self.authentication.authenticate(&mut request, &body);
+        }
+        RunnableRequest::new(request, body, &mut self.rng.lock().unwrap())
     }
 
     // TODO: when network does not find the address this is blocking the thread
     pub async fn send(&self, request: RunnableRequest) -> Result<ServiceResponse, reqwest::Error> {
         // TODO: ugly
+        let body = request.body().to_string();
         let requ = request.http_request();
         debug!("Sending request {:?}", requ);
         debug!("Request headers {:?}", requ.headers());
@@ -84,6 +110,7 @@ impl Service {
                 reqwest::Url::parse(&requ.uri().to_string()).unwrap(),
             )
             .headers(requ.headers().clone())
+            .body(body)
             .send()
             .await?;
 
@@ -19,6 +19,11 @@ pub fn validate(
         ));
     }
 
+    // Nothing else to check, there is no content to have a type or a body
+    if expectation.status_code == StatusCode::NO_CONTENT {
+        return Ok(());
+    }
+
     match response.content_type {
         None => {
             return Err(Disparity::IncorrectContentType(String::from(
@@ -46,7 +51,7 @@ pub fn validate(
         return Ok(());
     }
 
-    let schema_body = extract_schema(expectation.status_code, expectation.body)?;
+    let schema_body = extract_schema(expectation.status_code, expectation.body.as_ref())?;
 
     validate_schema(&response_body, schema_body)
 }
@@ -60,7 +65,7 @@ pub fn validate(
 // }
 
 fn is_application_defined_code(expected: StatusCode) -> bool {
-    let appplication_codes = vec!["200", "400", "403", "404", "409", "422", "423"];
+    let appplication_codes = vec!["200", "201", "400", "403", "404", "409", "422", "423"];
     appplication_codes
         .iter()
         .any(|&code| code == expected.as_str())
@@ -132,7 +137,6 @@ fn validate_schema(
 
 // TODO: It seems if the examples have keywords like "type" things will blow up, remove examples content
 // TODO: Do all the proper conversions here with all the differences between formats.
-// Most notably   nullable: true  -> [type, null]
 // See https://github.com/mikunn/openapi-schema-to-json-schema
 fn openapi_schema_to_json_schema(schema_data: &openapiv3::Schema) -> serde_json::Value {
     let mut the_schema = schema_data.clone();
@@ -140,11 +144,58 @@ fn openapi_schema_to_json_schema(schema_data: &openapiv3::Schema) -> serde_json:
 
     let serialized = serde_json::to_string(&the_schema).expect("Improper serialization of schema");
 
-    let json_v4_schema: serde_json::Value =
+    let mut json_v4_schema: serde_json::Value =
         serde_json::from_str(&serialized).expect("Improper deser of schema");
+    nullable_to_null_type(&mut json_v4_schema);
     json_v4_schema
 }
 
+// JSON schema has no `nullable`, null is one more type. OpenAPI 3.1 `type: [string, "null"]` ends here too.
+// Only the positions holding schemas are visited, a property can be named `nullable`.
+fn nullable_to_null_type(schema: &mut serde_json::Value) {
+    let map = match schema {
+        serde_json::Value::Object(map) => map,
+        _ => return,
+    };
+    for (keyword, value) in map.iter_mut() {
+        match (keyword.as_str(), value) {
+            ("items", value) | ("additionalProperties", value) | ("not", value) => {
+                nullable_to_null_type(value)
+            }
+            ("allOf", serde_json::Value::Array(schemas))
+            | ("anyOf", serde_json::Value::Array(schemas))
+            | ("oneOf", serde_json::Value::Array(schemas)) => {
+                for schema in schemas {
+                    nullable_to_null_type(schema);
+                }
+            }
+            ("properties", serde_json::Value::Object(properties)) => {
+                for property in properties.values_mut() {
+                    nullable_to_null_type(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(serde_json::Value::Bool(nullable)) = map.get("nullable").cloned() {
+        map.remove("nullable");
+        if nullable {
+            match map.remove("type") {
+                Some(serde_json::Value::String(the_type)) => {
+                    map.insert("type".to_string(), serde_json::json!([the_type, "null"]));
+                }
+                _ => {
+                    let not_null = serde_json::Value::Object(std::mem::take(map));
+                    map.insert(
+                        "anyOf".to_string(),
+                        serde_json::json!([not_null, {"type": "null"}]),
+                    );
+                }
+            }
+        }
+    }
+}
+
 // It seems Valico does not like having keywords like "type" in json examples.
 // Hacking around by just removing the examples, they are not needed anyways for validation
 fn remove_examples(schema: &mut openapiv3::Schema, recursion: i32) {